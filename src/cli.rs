@@ -1,16 +1,27 @@
 use clap::error::ErrorKind;
-use clap::{Args, CommandFactory, Parser};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use stone_prover_sdk::models::{Layout, Verifier};
 
+use crate::toolkit::sierra::FunctionArgs;
+
 #[derive(Parser, Debug)]
 #[command(name = "stone")]
 #[command(bin_name = "stone")]
 pub enum Cli {
+    Run(RunArgs),
     Prove(ProveArgs),
     Verify(VerifyArgs),
+    #[command(subcommand)]
+    Proof(ProofCommand),
+    Fact(FactArgs),
+    #[command(subcommand)]
+    Params(ParamsCommand),
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    Batch(BatchArgs),
 }
 
 #[derive(Debug, Clone)]
@@ -46,79 +57,397 @@ impl FromStr for Bootloader {
     }
 }
 
-#[derive(Args, Debug)]
-#[command(args_conflicts_with_subcommands = true)]
-#[command(flatten_help = true)]
-pub struct ProveArgs {
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashFunction {
+    #[default]
+    Pedersen,
+    Poseidon,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ExecutionArgs {
     #[clap(long = "with-bootloader", default_value_t = false)]
     pub with_bootloader: bool,
 
     #[clap(long = "bootloader-version")]
-    bootloader: Option<Bootloader>,
+    pub bootloader: Option<Bootloader>,
 
+    /// Defaults to the cheapest layout that supports the builtins and fits the trace
+    /// of the program.
     #[clap(long = "layout")]
     pub layout: Option<Layout>,
 
+    #[clap(long = "allow-missing-builtins", action)]
+    pub allow_missing_builtins: bool,
+
+    #[clap(long = "cairo-pie-output")]
+    pub cairo_pie_output: Option<PathBuf>,
+
+    /// Write the steps spent in each function as folded stacks, for flamegraph tools.
+    /// Only supported for Cairo 0 programs run without the bootloader.
+    #[clap(long = "profile", conflicts_with = "with_bootloader")]
+    pub profile: Option<PathBuf>,
+
+    /// JSON file made available to the hints of the program as `program_input`. Only hints
+    /// assigning `program_input[...]`, `len(program_input[...])` or
+    /// `segments.gen_arg(program_input[...])` to `ids.<name>` can read it.
+    #[clap(long = "program-input")]
+    pub program_input: Option<PathBuf>,
+
+    /// Arguments of the `main` function of a Sierra program, e.g. "1 [2 3] 4".
+    #[clap(long = "args")]
+    pub args: Option<FunctionArgs>,
+
+    /// JSON or TOML file listing the bootloader tasks, with their program input and entrypoint.
+    /// Only Pedersen program hashes are supported.
+    #[clap(long = "task-manifest", requires = "with_bootloader")]
+    pub task_manifest: Option<PathBuf>,
+
+    /// Function to run instead of `main`. In bootloader mode, applies to all the program tasks.
+    #[clap(long = "entrypoint")]
+    pub entrypoint: Option<String>,
+
+    /// Simple bootloader input file in the cairo-lang format. Replaces the positional programs.
+    /// Tasks with `use_poseidon` set are rejected, only Pedersen program hashes are supported.
+    #[clap(
+        long = "bootloader-input",
+        requires = "with_bootloader",
+        conflicts_with_all = ["programs", "task_manifest"]
+    )]
+    pub bootloader_input: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub limits: ExecutionLimits,
+
+    #[clap(flatten)]
+    pub cairo_run_files: CairoRunFiles,
+
+    /// Format of the execution resource report printed once the execution completes.
+    #[clap(long = "report", value_enum, default_value_t = OutputFormat::Text)]
+    pub report: OutputFormat,
+
+    #[arg(num_args = 1..)]
+    pub programs: Vec<PathBuf>,
+}
+
+/// Guard rails for the execution of untrusted programs.
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct ExecutionLimits {
+    /// Abort the execution after this number of steps. In bootloader mode, the limit applies
+    /// to the steps of the tasks, the bootloader itself is not accounted for. Not supported
+    /// for Sierra programs, which cannot be interrupted.
+    #[clap(long = "max-steps")]
+    pub max_steps: Option<usize>,
+
+    /// Abort if the execution uses more memory cells. Only checked once the execution
+    /// completes, before proving: use `--max-steps` to bound the execution itself.
+    #[clap(long = "max-memory-cells")]
+    pub max_memory_cells: Option<usize>,
+}
+
+/// Execution artifacts written to individual files, as by cairo-lang's `cairo-run`.
+#[derive(Args, Clone, Debug, Default)]
+pub struct CairoRunFiles {
+    /// Write the relocated trace, in the binary format of `cairo-run --trace_file`.
+    #[clap(long = "trace-file")]
+    pub trace_file: Option<PathBuf>,
+
+    /// Write the relocated memory, in the binary format of `cairo-run --memory_file`.
+    #[clap(long = "memory-file")]
+    pub memory_file: Option<PathBuf>,
+
+    #[clap(long = "air-public-input")]
+    pub air_public_input: Option<PathBuf>,
+
+    /// References the trace and memory files by their absolute path, like `cairo-run`.
+    #[clap(long = "air-private-input", requires_all = ["trace_file", "memory_file"])]
+    pub air_private_input: Option<PathBuf>,
+}
+
+/// Incompatible execution options. Reported by clap on the command line, and as a job
+/// error by `stone batch`.
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ExecutionArgsError {
+    #[error("At least one program must be specified")]
+    MissingProgram,
+
+    #[error("Cannot specify programs or a task manifest with a bootloader input")]
+    TasksWithBootloaderInput,
+
+    #[error("Cannot export a Cairo PIE in bootloader mode")]
+    CairoPieWithBootloader,
+
+    #[error("Cannot specify a program input in bootloader mode")]
+    ProgramInputWithBootloader,
+
+    #[error(
+        "Cannot specify function arguments in bootloader mode, set them in a task manifest instead"
+    )]
+    ArgsWithBootloader,
+
+    #[error("Cannot specify a task manifest or bootloader input in no-bootloader mode")]
+    TasksWithoutBootloader,
+
+    #[error("Cannot specify bootloader version in no-bootloader mode")]
+    BootloaderVersionWithoutBootloader,
+
+    #[error("Cannot run multiple programs in no-bootloader mode")]
+    MultiplePrograms,
+
+    #[error("Cannot specify fact topologies file in no-bootloader mode")]
+    FactTopologiesWithoutBootloader,
+}
+
+impl ExecutionArgsError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::MissingProgram => ErrorKind::MissingRequiredArgument,
+            _ => ErrorKind::ArgumentConflict,
+        }
+    }
+}
+
+impl ExecutionArgs {
+    /// Checks that a program is specified and that the arguments are compatible with
+    /// the execution mode.
+    pub fn validate(&self, fact_topologies_file: Option<&Path>) -> Result<(), ExecutionArgsError> {
+        if self.programs.is_empty()
+            && self.task_manifest.is_none()
+            && self.bootloader_input.is_none()
+        {
+            return Err(ExecutionArgsError::MissingProgram);
+        }
+        if self.bootloader_input.is_some()
+            && (!self.programs.is_empty() || self.task_manifest.is_some())
+        {
+            return Err(ExecutionArgsError::TasksWithBootloaderInput);
+        }
+        if self.with_bootloader {
+            if self.cairo_pie_output.is_some() {
+                return Err(ExecutionArgsError::CairoPieWithBootloader);
+            }
+            if self.program_input.is_some() {
+                return Err(ExecutionArgsError::ProgramInputWithBootloader);
+            }
+            if self.args.is_some() {
+                return Err(ExecutionArgsError::ArgsWithBootloader);
+            }
+        } else {
+            if self.task_manifest.is_some() || self.bootloader_input.is_some() {
+                return Err(ExecutionArgsError::TasksWithoutBootloader);
+            }
+            if self.bootloader.is_some() {
+                return Err(ExecutionArgsError::BootloaderVersionWithoutBootloader);
+            }
+            if self.programs.len() > 1 {
+                return Err(ExecutionArgsError::MultiplePrograms);
+            }
+            if fact_topologies_file.is_some() {
+                return Err(ExecutionArgsError::FactTopologiesWithoutBootloader);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exits with an error if the arguments are invalid, see `validate`.
+    fn validate_or_exit(&self, cmd: &mut clap::Command, fact_topologies_file: Option<&Path>) {
+        if let Err(e) = self.validate(fact_topologies_file) {
+            cmd.error(e.kind(), e).exit();
+        }
+    }
+
+    pub fn executable(mut self, default_bootloader: Bootloader) -> Executable {
+        match self.with_bootloader {
+            true => {
+                let bootloader = self.bootloader.unwrap_or(default_bootloader);
+                Executable::WithBootloader {
+                    bootloader,
+                    tasks: self.programs,
+                    task_manifest: self.task_manifest,
+                    bootloader_input: self.bootloader_input,
+                    entrypoint: self.entrypoint,
+                }
+            }
+            false => Executable::BareMetal {
+                program: self.programs.remove(0),
+                program_input: self.program_input,
+                args: self.args,
+                entrypoint: self.entrypoint,
+            },
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+#[command(flatten_help = true)]
+pub struct RunArgs {
+    #[clap(flatten)]
+    pub execution: ExecutionArgs,
+
+    #[clap(long = "output-dir")]
+    pub output_dir: Option<PathBuf>,
+
+    #[clap(long = "fact-topologies-file")]
+    pub fact_topologies_file: Option<PathBuf>,
+
+    /// Verifier of the proof of the execution, selects the layout and bootloader
+    /// accordingly. Prove the artifacts with the same verifier.
     #[clap(long = "verifier")]
     pub verifier: Option<Verifier>,
+}
 
-    #[clap(long = "allow-missing-builtins", action)]
+impl RunArgs {
+    pub fn command(self) -> RunCommand {
+        let mut cmd = Cli::command();
+        self.execution
+            .validate_or_exit(&mut cmd, self.fact_topologies_file.as_deref());
+
+        let layout = self.execution.layout;
+        let allow_missing_builtins = self.execution.allow_missing_builtins;
+        let limits = self.execution.limits;
+        let report = self.execution.report;
+        let cairo_pie_output = self.execution.cairo_pie_output.clone();
+        let profile = self.execution.profile.clone();
+        let cairo_run_files = self.execution.cairo_run_files.clone();
+        let verifier = self.verifier.unwrap_or(Verifier::Stone);
+        let executable = self
+            .execution
+            .executable(Bootloader::latest_compatible(&verifier));
+        let output_dir = self.output_dir.unwrap_or(PathBuf::from("."));
+
+        RunCommand {
+            executable,
+            layout,
+            verifier,
+            allow_missing_builtins,
+            limits,
+            report: Some(report),
+            cairo_pie_output,
+            profile,
+            cairo_run_files,
+            output_dir,
+            fact_topologies_file: self.fact_topologies_file,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunCommand {
+    pub executable: Executable,
+    /// Selected automatically if not specified.
+    pub layout: Option<Layout>,
+    /// Restricts the layouts selected automatically.
+    pub verifier: Verifier,
     pub allow_missing_builtins: bool,
+    pub limits: ExecutionLimits,
+    /// Format of the execution report, not printed if `None`.
+    pub report: Option<OutputFormat>,
+    pub cairo_pie_output: Option<PathBuf>,
+    pub profile: Option<PathBuf>,
+    pub cairo_run_files: CairoRunFiles,
+    pub output_dir: PathBuf,
+    pub fact_topologies_file: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+#[command(flatten_help = true)]
+pub struct ProveArgs {
+    #[clap(flatten)]
+    pub execution: ExecutionArgs,
+
+    #[clap(long = "verifier")]
+    pub verifier: Option<Verifier>,
+
+    #[clap(flatten)]
+    pub artifacts: ExecutionArtifactsArgs,
 
     #[clap(flatten)]
     pub config: ConfigArgs,
 
-    #[arg(required = true, num_args = 1..)]
-    pub programs: Vec<PathBuf>,
+    #[clap(flatten)]
+    pub program_output: ProgramOutputArgs,
+
+    /// Keep the execution artifacts, prover config and parameters in this directory,
+    /// to run the Stone prover on them by hand.
+    #[clap(long = "keep-artifacts")]
+    pub keep_artifacts: Option<PathBuf>,
+}
+
+/// Options to display the output of the program once it is executed.
+#[derive(Args, Clone, Debug, Default)]
+pub struct ProgramOutputArgs {
+    /// Print the program output. In bootloader mode, the output of each task is printed.
+    #[clap(long = "print-output", action)]
+    pub print_output: bool,
+
+    /// Write the program output to a JSON file.
+    #[clap(long = "output-values-file")]
+    pub output_values_file: Option<PathBuf>,
+
+    #[clap(long = "output-encoding", value_enum, default_value_t = FeltEncoding::Decimal)]
+    pub output_encoding: FeltEncoding,
 }
 
 impl ProveArgs {
-    pub fn command(mut self) -> ProveCommand {
+    pub fn command(self) -> ProveCommand {
         let mut cmd = Cli::command();
-        if !self.with_bootloader {
+        let verifier = self.verifier.unwrap_or(Verifier::Stone);
+
+        if let Some(artifacts) = self.artifacts.paths() {
             if self.config.fact_topologies_file.is_some() {
                 cmd.error(
                     ErrorKind::ArgumentConflict,
-                    "Cannot specify fact topologies file in no-bootloader mode",
-                )
-                .exit();
-            }
-            if self.bootloader.is_some() {
-                cmd.error(
-                    ErrorKind::ArgumentConflict,
-                    "Cannot specify bootloader version in no-bootloader mode",
-                )
-                .exit();
-            }
-            if self.programs.len() > 1 {
-                cmd.error(
-                    ErrorKind::ArgumentConflict,
-                    "Cannot prove multiple programs in no-bootloader mode",
+                    "Cannot specify fact topologies file when proving pre-computed execution artifacts",
                 )
                 .exit();
             }
+            return ProveCommand {
+                executable: Executable::PreComputed(artifacts),
+                config: self.config,
+                // Unused, the layout is read from the public input
+                layout: self.execution.layout,
+                verifier,
+                allow_missing_builtins: self.execution.allow_missing_builtins,
+                limits: ExecutionLimits::default(),
+                report: Some(self.execution.report),
+                cairo_pie_output: None,
+                profile: None,
+                cairo_run_files: self.execution.cairo_run_files,
+                program_output: self.program_output,
+                keep_artifacts: self.keep_artifacts,
+            };
         }
 
-        let layout = self.layout.unwrap_or(Layout::StarknetWithKeccak);
-        let verifier = self.verifier.unwrap_or(Verifier::Stone);
+        self.execution
+            .validate_or_exit(&mut cmd, self.config.fact_topologies_file.as_deref());
 
-        let executable = match self.with_bootloader {
-            true => {
-                let bootloader = match self.bootloader {
-                    Some(version) => version,
-                    None => Bootloader::latest_compatible(&verifier),
-                };
-                Executable::WithBootloader(bootloader, self.programs)
-            }
-            false => Executable::BareMetal(self.programs.remove(0)),
-        };
+        let layout = self.execution.layout;
+        let allow_missing_builtins = self.execution.allow_missing_builtins;
+        let limits = self.execution.limits;
+        let report = self.execution.report;
+        let cairo_pie_output = self.execution.cairo_pie_output.clone();
+        let profile = self.execution.profile.clone();
+        let cairo_run_files = self.execution.cairo_run_files.clone();
+        let executable = self
+            .execution
+            .executable(Bootloader::latest_compatible(&verifier));
 
         ProveCommand {
             executable,
             config: self.config,
             layout,
             verifier,
-            allow_missing_builtins: self.allow_missing_builtins,
+            allow_missing_builtins,
+            limits,
+            report: Some(report),
+            cairo_pie_output,
+            profile,
+            cairo_run_files,
+            program_output: self.program_output,
+            keep_artifacts: self.keep_artifacts,
         }
     }
 }
@@ -127,15 +456,92 @@ impl ProveArgs {
 pub struct ProveCommand {
     pub executable: Executable,
     pub config: ConfigArgs,
-    pub layout: Layout,
+    /// Selected automatically if not specified.
+    pub layout: Option<Layout>,
     pub verifier: Verifier,
     pub allow_missing_builtins: bool,
+    pub limits: ExecutionLimits,
+    /// Format of the execution report, not printed if `None`.
+    pub report: Option<OutputFormat>,
+    pub cairo_pie_output: Option<PathBuf>,
+    pub profile: Option<PathBuf>,
+    pub cairo_run_files: CairoRunFiles,
+    pub program_output: ProgramOutputArgs,
+    /// Directory where the inputs of the prover are written before proving.
+    pub keep_artifacts: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Executable {
-    BareMetal(PathBuf),
-    WithBootloader(Bootloader, Vec<PathBuf>),
+    BareMetal {
+        program: PathBuf,
+        program_input: Option<PathBuf>,
+        /// Arguments of the `main` function of Sierra programs.
+        args: Option<FunctionArgs>,
+        /// Function called by `__start__` instead of `main`, for Cairo 0 programs.
+        entrypoint: Option<String>,
+    },
+    WithBootloader {
+        bootloader: Bootloader,
+        tasks: Vec<PathBuf>,
+        task_manifest: Option<PathBuf>,
+        bootloader_input: Option<PathBuf>,
+        entrypoint: Option<String>,
+    },
+    PreComputed(ExecutionArtifactsPaths),
+}
+
+/// Paths to execution artifacts generated ahead of time, e.g. by `stone run`.
+#[derive(Debug, Clone)]
+pub struct ExecutionArtifactsPaths {
+    pub public_input: PathBuf,
+    pub private_input: PathBuf,
+    pub memory: PathBuf,
+    pub trace: PathBuf,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct ExecutionArtifactsArgs {
+    #[clap(
+        long = "public-input",
+        requires_all = ["private_input", "memory", "trace"],
+        conflicts_with_all = [
+            "programs",
+            "with_bootloader",
+            "bootloader",
+            "layout",
+            "allow_missing_builtins",
+            "cairo_pie_output",
+            "program_input",
+            "args",
+            "task_manifest",
+            "entrypoint",
+            "bootloader_input",
+            "profile",
+            "max_steps",
+            "max_memory_cells",
+        ]
+    )]
+    pub public_input: Option<PathBuf>,
+    #[clap(long = "private-input", requires = "public_input")]
+    pub private_input: Option<PathBuf>,
+    #[clap(long = "memory", requires = "public_input")]
+    pub memory: Option<PathBuf>,
+    #[clap(long = "trace", requires = "public_input")]
+    pub trace: Option<PathBuf>,
+}
+
+impl ExecutionArtifactsArgs {
+    /// Returns the artifact paths if the user asked to skip execution.
+    /// Clap guarantees that either all or none of the paths are specified.
+    fn paths(&self) -> Option<ExecutionArtifactsPaths> {
+        Some(ExecutionArtifactsPaths {
+            public_input: self.public_input.clone()?,
+            private_input: self.private_input.clone()?,
+            memory: self.memory.clone()?,
+            trace: self.trace.clone()?,
+        })
+    }
 }
 
 #[derive(Args, Clone, Debug)]
@@ -162,4 +568,264 @@ impl ConfigArgs {
 #[derive(Args, Clone, Debug)]
 pub struct VerifyArgs {
     pub proof_file: PathBuf,
+
+    /// Expected program output: a file or an inline list of felts, e.g. "[1, 2, 0x3]".
+    #[clap(long = "expected-output")]
+    pub expected_output: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProofCommand {
+    /// Print a summary of the parameters and public input of a proof.
+    Inspect(InspectArgs),
+    /// Split a proof into the files expected by the Starknet L1 verifier contracts.
+    Split(SplitProofArgs),
+}
+
+/// How to display felts.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeltEncoding {
+    #[default]
+    Decimal,
+    /// Values above half the field prime are displayed as negative integers.
+    Signed,
+    Hex,
+    /// Cairo short strings, values that are not valid ASCII are displayed in hexadecimal.
+    ShortString,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct InspectArgs {
+    pub proof_file: PathBuf,
+
+    #[clap(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct SplitProofArgs {
+    pub proof_file: PathBuf,
+
+    #[clap(long = "output-dir")]
+    pub output_dir: Option<PathBuf>,
+}
+
+impl SplitProofArgs {
+    pub fn output_dir(&self) -> Cow<PathBuf> {
+        match self.output_dir.as_ref() {
+            Some(path) => Cow::Borrowed(path),
+            None => Cow::Owned(PathBuf::from("split-proof")),
+        }
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct FactArgs {
+    /// Proof file from which the program output is read.
+    #[clap(long = "proof")]
+    pub proof_file: Option<PathBuf>,
+
+    /// Compiled Cairo 0 program, used to compute the program hash.
+    #[clap(long = "program")]
+    pub program_file: Option<PathBuf>,
+
+    /// Function used as the entrypoint of the program when computing its hash.
+    #[clap(long = "entrypoint", default_value = "main")]
+    pub entrypoint: String,
+
+    /// Program output: a file or an inline list of felts. Cannot be used with `--proof`.
+    #[clap(long = "output", conflicts_with = "proof_file")]
+    pub output: Option<String>,
+
+    /// Compute one fact per task from the output of a bootloader proof.
+    #[clap(
+        long = "with-bootloader",
+        default_value_t = false,
+        requires = "proof_file"
+    )]
+    pub with_bootloader: bool,
+
+    #[clap(long = "fact-topologies-file", requires = "with_bootloader")]
+    pub fact_topologies_file: Option<PathBuf>,
+
+    #[clap(long = "hash-function", value_enum, default_value_t = HashFunction::Pedersen)]
+    pub hash_function: HashFunction,
+
+    #[clap(long = "format", value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone)]
+pub enum FactSource {
+    /// A program and its output, read from a proof or specified by the user.
+    Program {
+        program_file: PathBuf,
+        proof_file: Option<PathBuf>,
+        output: Option<String>,
+        hash_function: HashFunction,
+        entrypoint: String,
+    },
+    /// A bootloader proof, the program hashes are read from the bootloader output.
+    Bootloader {
+        proof_file: PathBuf,
+        fact_topologies_file: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct FactCommand {
+    pub source: FactSource,
+    pub format: OutputFormat,
+}
+
+impl FactArgs {
+    pub fn command(self) -> FactCommand {
+        let mut cmd = Cli::command();
+
+        let source = match (self.with_bootloader, self.proof_file) {
+            (true, Some(proof_file)) => {
+                if self.program_file.is_some() {
+                    cmd.error(
+                        ErrorKind::ArgumentConflict,
+                        "Cannot specify a program in bootloader mode, the program hashes are read from the proof",
+                    )
+                    .exit();
+                }
+                FactSource::Bootloader {
+                    proof_file,
+                    fact_topologies_file: self.fact_topologies_file,
+                }
+            }
+            (_, proof_file) => {
+                let Some(program_file) = self.program_file else {
+                    cmd.error(
+                        ErrorKind::MissingRequiredArgument,
+                        "A program is required to compute the program hash",
+                    )
+                    .exit();
+                };
+                if proof_file.is_none() && self.output.is_none() {
+                    cmd.error(
+                        ErrorKind::MissingRequiredArgument,
+                        "Either a proof or the program output must be specified",
+                    )
+                    .exit();
+                }
+                FactSource::Program {
+                    program_file,
+                    proof_file,
+                    output: self.output,
+                    hash_function: self.hash_function,
+                    entrypoint: self.entrypoint,
+                }
+            }
+        };
+
+        FactCommand {
+            source,
+            format: self.format,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ParamsCommand {
+    /// Generate the prover parameters for a given number of steps.
+    Generate(GenerateParamsArgs),
+}
+
+/// Options that determine the prover parameters.
+#[derive(Args, Clone, Debug)]
+pub struct ParamsArgs {
+    #[clap(
+        long = "n-steps",
+        required_unless_present = "program",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub n_steps: Option<u32>,
+
+    /// Run this program to determine the number of steps.
+    #[clap(long = "from-program", conflicts_with = "n_steps")]
+    pub program: Option<PathBuf>,
+
+    #[clap(long = "program-input", requires = "program")]
+    pub program_input: Option<PathBuf>,
+
+    #[clap(long = "args", requires = "program")]
+    pub args: Option<FunctionArgs>,
+
+    #[clap(long = "layout", requires = "program")]
+    pub layout: Option<Layout>,
+
+    #[clap(long = "allow-missing-builtins", action, requires = "program")]
+    pub allow_missing_builtins: bool,
+
+    #[clap(long = "verifier")]
+    pub verifier: Option<Verifier>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct GenerateParamsArgs {
+    #[clap(flatten)]
+    pub params: ParamsArgs,
+
+    #[clap(long = "output-file")]
+    pub output_file: Option<PathBuf>,
+}
+
+impl GenerateParamsArgs {
+    pub fn output_file(&self) -> Cow<PathBuf> {
+        match self.output_file.as_ref() {
+            Some(path) => Cow::Borrowed(path),
+            None => Cow::Owned(PathBuf::from("cpu_air_params.json")),
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write the default prover config and the prover parameters to a directory.
+    Init(InitConfigArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct InitConfigArgs {
+    #[clap(flatten)]
+    pub params: ParamsArgs,
+
+    #[clap(long = "output-dir")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Overwrite existing files.
+    #[clap(long = "force", action)]
+    pub force: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct BatchArgs {
+    /// TOML file describing the jobs to prove.
+    pub manifest: PathBuf,
+
+    /// Maximum number of jobs proved in parallel.
+    #[clap(long = "concurrency", default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub concurrency: u32,
+
+    #[clap(long = "report-file")]
+    pub report_file: Option<PathBuf>,
+}
+
+impl BatchArgs {
+    pub fn report_file(&self) -> Cow<PathBuf> {
+        match self.report_file.as_ref() {
+            Some(path) => Cow::Borrowed(path),
+            None => Cow::Owned(PathBuf::from("batch_report.json")),
+        }
+    }
 }
@@ -0,0 +1,507 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use stone_prover_sdk::models::{Layout, Verifier};
+
+use crate::cli::{
+    BatchArgs, Bootloader, CairoRunFiles, ConfigArgs, ExecutionArgs, ExecutionLimits, OutputFormat,
+    ProgramOutputArgs, ProveCommand,
+};
+use crate::commands::prove::prove;
+use crate::toolkit::json::write_pretty_json_to_file;
+use crate::toolkit::sierra::FunctionArgs;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BatchError {
+    #[error("Failed to access file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Failed to deserialize {0}: {1}")]
+    Deserialize(PathBuf, toml::de::Error),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Several jobs are named {0}")]
+    DuplicateJobName(String),
+
+    #[error("Several jobs write their proof to {0}")]
+    DuplicateOutputFile(PathBuf),
+
+    #[error("{0} out of {1} jobs failed")]
+    FailedJobs(usize, usize),
+}
+
+/// A single proving job, mirrors the options of `stone prove`.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct BatchJob {
+    name: Option<String>,
+    #[serde(default)]
+    programs: Vec<PathBuf>,
+    program_input: Option<PathBuf>,
+    args: Option<FunctionArgs>,
+    task_manifest: Option<PathBuf>,
+    bootloader_input: Option<PathBuf>,
+    entrypoint: Option<String>,
+    #[serde(default)]
+    with_bootloader: bool,
+    bootloader_version: Option<String>,
+    layout: Option<Layout>,
+    verifier: Option<Verifier>,
+    #[serde(default)]
+    allow_missing_builtins: bool,
+    max_steps: Option<usize>,
+    max_memory_cells: Option<usize>,
+    prover_config_file: Option<PathBuf>,
+    parameter_file: Option<PathBuf>,
+    output_file: Option<PathBuf>,
+    fact_topologies_file: Option<PathBuf>,
+    keep_artifacts: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct BatchManifest {
+    jobs: Vec<BatchJob>,
+}
+
+impl BatchJob {
+    /// Returns the name of the job, defaults to the name of its first program.
+    fn name(&self, index: usize) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            self.programs
+                .first()
+                .and_then(|program| program.file_stem())
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| format!("job-{index}"))
+        })
+    }
+
+    /// Builds the prove command of the job. Relative paths are resolved from `base_dir`,
+    /// the directory of the manifest.
+    fn command(&self, name: &str, base_dir: &Path) -> Result<ProveCommand, String> {
+        let resolve = |path: &PathBuf| base_dir.join(path);
+        let bootloader = match self.bootloader_version.as_deref() {
+            Some(version) => Some(match Bootloader::from_str(version)? {
+                Bootloader::Custom(path) => Bootloader::Custom(resolve(&path)),
+                bootloader => bootloader,
+            }),
+            None => None,
+        };
+        let execution = ExecutionArgs {
+            with_bootloader: self.with_bootloader,
+            bootloader,
+            layout: self.layout,
+            allow_missing_builtins: self.allow_missing_builtins,
+            cairo_pie_output: None,
+            profile: None,
+            program_input: self.program_input.as_ref().map(resolve),
+            args: self.args.clone(),
+            task_manifest: self.task_manifest.as_ref().map(resolve),
+            entrypoint: self.entrypoint.clone(),
+            bootloader_input: self.bootloader_input.as_ref().map(resolve),
+            limits: ExecutionLimits {
+                max_steps: self.max_steps,
+                max_memory_cells: self.max_memory_cells,
+            },
+            cairo_run_files: CairoRunFiles::default(),
+            report: OutputFormat::Text,
+            programs: self.programs.iter().map(resolve).collect(),
+        };
+        execution
+            .validate(self.fact_topologies_file.as_deref())
+            .map_err(|e| e.to_string())?;
+
+        let verifier = self.verifier.unwrap_or(Verifier::Stone);
+        let output_file = match self.output_file.as_ref() {
+            Some(path) => resolve(path),
+            None => base_dir.join(format!("{name}_proof.json")),
+        };
+
+        Ok(ProveCommand {
+            executable: execution.executable(Bootloader::latest_compatible(&verifier)),
+            config: ConfigArgs {
+                prover_config_file: self.prover_config_file.as_ref().map(resolve),
+                parameter_file: self.parameter_file.as_ref().map(resolve),
+                output_file: Some(output_file),
+                fact_topologies_file: self.fact_topologies_file.as_ref().map(resolve),
+            },
+            layout: self.layout,
+            verifier,
+            allow_missing_builtins: self.allow_missing_builtins,
+            limits: ExecutionLimits {
+                max_steps: self.max_steps,
+                max_memory_cells: self.max_memory_cells,
+            },
+            // Reports of concurrent jobs would be interleaved
+            report: None,
+            cairo_pie_output: None,
+            profile: None,
+            cairo_run_files: CairoRunFiles::default(),
+            program_output: ProgramOutputArgs::default(),
+            keep_artifacts: self.keep_artifacts.as_ref().map(resolve),
+        })
+    }
+}
+
+/// Returns the canonical form of an output file, to detect paths that point to the same
+/// file. The file usually does not exist yet, so only its directory is canonicalized.
+fn canonical_output_file(path: &Path) -> PathBuf {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    match (dir.canonicalize(), path.file_name()) {
+        (Ok(dir), Some(file_name)) => dir.join(file_name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Rejects jobs sharing a name or an output file: their reports would be ambiguous and
+/// their proofs would overwrite each other.
+fn check_duplicate_jobs(jobs: &[(String, Result<ProveCommand, String>)]) -> Result<(), BatchError> {
+    let mut names = HashSet::new();
+    let mut output_files = HashSet::new();
+    for (name, command) in jobs {
+        if !names.insert(name) {
+            return Err(BatchError::DuplicateJobName(name.clone()));
+        }
+        let output_file = command
+            .as_ref()
+            .ok()
+            .and_then(|command| command.config.output_file.as_ref());
+        if let Some(output_file) = output_file {
+            if !output_files.insert(canonical_output_file(output_file)) {
+                return Err(BatchError::DuplicateOutputFile(output_file.clone()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Debug)]
+struct JobReport {
+    name: String,
+    status: JobStatus,
+    output_file: Option<PathBuf>,
+    error: Option<String>,
+    duration_secs: f64,
+}
+
+#[derive(Serialize, Debug)]
+struct BatchReport {
+    n_jobs: usize,
+    n_succeeded: usize,
+    n_failed: usize,
+    jobs: Vec<JobReport>,
+}
+
+/// Returns the message passed to `panic!`.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown error")
+}
+
+/// Runs a job, turning a panic into an error so that it does not abort the other jobs.
+fn catch_panic<T>(job: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    std::panic::catch_unwind(AssertUnwindSafe(job))
+        .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(&*payload))))
+}
+
+fn run_job(name: String, command: Result<ProveCommand, String>) -> JobReport {
+    let start = Instant::now();
+    let output_file = command
+        .as_ref()
+        .ok()
+        .and_then(|command| command.config.output_file.clone());
+
+    info!("[{name}] started.");
+    let result =
+        command.and_then(|command| catch_panic(|| prove(command).map_err(|e| e.to_string())));
+    let duration_secs = start.elapsed().as_secs_f64();
+
+    match result {
+        Ok(()) => {
+            info!("[{name}] completed in {duration_secs:.1}s.");
+            JobReport {
+                name,
+                status: JobStatus::Succeeded,
+                output_file,
+                error: None,
+                duration_secs,
+            }
+        }
+        Err(e) => {
+            error!("[{name}] {e}");
+            JobReport {
+                name,
+                status: JobStatus::Failed,
+                output_file: None,
+                error: Some(e),
+                duration_secs,
+            }
+        }
+    }
+}
+
+pub fn batch(args: BatchArgs) -> Result<(), BatchError> {
+    let content = std::fs::read_to_string(&args.manifest)
+        .map_err(|e| BatchError::Io(args.manifest.clone(), e))?;
+    let manifest: BatchManifest =
+        toml::from_str(&content).map_err(|e| BatchError::Deserialize(args.manifest.clone(), e))?;
+    let base_dir = args.manifest.parent().unwrap_or(Path::new("."));
+
+    // Invalid jobs are reported like failed ones instead of aborting the whole batch.
+    let jobs: Vec<(String, Result<ProveCommand, String>)> = manifest
+        .jobs
+        .iter()
+        .enumerate()
+        .map(|(index, job)| {
+            let name = job.name(index);
+            let command = job.command(&name, base_dir);
+            (name, command)
+        })
+        .collect();
+    check_duplicate_jobs(&jobs)?;
+    let n_jobs = jobs.len();
+    info!(
+        "proving {n_jobs} jobs with a concurrency of {}...",
+        args.concurrency
+    );
+
+    let next_job = AtomicUsize::new(0);
+    let job_reports: Mutex<Vec<Option<JobReport>>> =
+        Mutex::new((0..n_jobs).map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..(args.concurrency as usize).min(n_jobs) {
+            scope.spawn(|| loop {
+                let index = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some((name, command)) = jobs.get(index) else {
+                    break;
+                };
+                let job_report = run_job(name.clone(), command.clone());
+                job_reports.lock().unwrap()[index] = Some(job_report);
+            });
+        }
+    });
+
+    // All jobs have been processed once the scope exits
+    let job_reports: Vec<JobReport> = job_reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect();
+    let n_failed = job_reports
+        .iter()
+        .filter(|job_report| job_report.status == JobStatus::Failed)
+        .count();
+    let report = BatchReport {
+        n_jobs,
+        n_succeeded: n_jobs - n_failed,
+        n_failed,
+        jobs: job_reports,
+    };
+
+    let report_file = args.report_file();
+    write_pretty_json_to_file(&report, report_file.as_ref())
+        .map_err(|e| BatchError::Io(report_file.to_path_buf(), e))?;
+    info!(
+        "{}/{n_jobs} jobs succeeded, report written to {}.",
+        report.n_succeeded,
+        report_file.to_string_lossy()
+    );
+
+    if n_failed > 0 {
+        return Err(BatchError::FailedJobs(n_failed, n_jobs));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Executable, ExecutionArgsError};
+
+    fn parse_jobs(manifest: &str) -> Vec<BatchJob> {
+        toml::from_str::<BatchManifest>(manifest).unwrap().jobs
+    }
+
+    #[test]
+    fn test_job_command_resolves_paths() {
+        let jobs = parse_jobs(
+            r#"
+            [[jobs]]
+            programs = ["programs/fibonacci.json"]
+            layout = "recursive"
+            verifier = "l1"
+            parameter_file = "/etc/params.json"
+            "#,
+        );
+        let name = jobs[0].name(0);
+        assert_eq!(name, "fibonacci");
+
+        let command = jobs[0].command(&name, Path::new("jobs")).unwrap();
+        assert!(matches!(
+            &command.executable,
+            Executable::BareMetal { program, .. } if program == Path::new("jobs/programs/fibonacci.json")
+        ));
+        assert!(matches!(command.layout, Some(Layout::Recursive)));
+        assert!(matches!(command.verifier, Verifier::L1));
+        assert_eq!(
+            command.config.parameter_file,
+            Some(PathBuf::from("/etc/params.json"))
+        );
+        assert_eq!(
+            command.config.output_file,
+            Some(PathBuf::from("jobs/fibonacci_proof.json"))
+        );
+    }
+
+    #[test]
+    fn test_job_command_rejects_multiple_programs_without_bootloader() {
+        let jobs = parse_jobs(
+            r#"
+            [[jobs]]
+            name = "invalid"
+            programs = ["a.json", "b.json"]
+            "#,
+        );
+        assert!(jobs[0].command("invalid", Path::new(".")).is_err());
+    }
+
+    #[test]
+    fn test_job_command_uses_shared_validation() {
+        let jobs = parse_jobs(
+            r#"
+            [[jobs]]
+            programs = ["a.json"]
+            with_bootloader = true
+            program_input = "input.json"
+            "#,
+        );
+        assert_eq!(
+            jobs[0].command("a", Path::new(".")).unwrap_err(),
+            ExecutionArgsError::ProgramInputWithBootloader.to_string()
+        );
+    }
+
+    fn jobs_with_commands(manifest: &str) -> Vec<(String, Result<ProveCommand, String>)> {
+        parse_jobs(manifest)
+            .iter()
+            .enumerate()
+            .map(|(index, job)| {
+                let name = job.name(index);
+                let command = job.command(&name, Path::new("jobs"));
+                (name, command)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_duplicate_job_names() {
+        let jobs = jobs_with_commands(
+            r#"
+            [[jobs]]
+            programs = ["a/fibonacci.json"]
+
+            [[jobs]]
+            programs = ["b/fibonacci.json"]
+            output_file = "b.json"
+            "#,
+        );
+        assert!(matches!(
+            check_duplicate_jobs(&jobs),
+            Err(BatchError::DuplicateJobName(name)) if name == "fibonacci"
+        ));
+    }
+
+    #[test]
+    fn test_check_duplicate_output_files() {
+        let jobs = jobs_with_commands(
+            r#"
+            [[jobs]]
+            name = "a"
+            programs = ["a.json"]
+            output_file = "proof.json"
+
+            [[jobs]]
+            name = "b"
+            programs = ["b.json"]
+            output_file = "./proof.json"
+            "#,
+        );
+        assert!(matches!(
+            check_duplicate_jobs(&jobs),
+            Err(BatchError::DuplicateOutputFile(path)) if path == Path::new("jobs/proof.json")
+        ));
+    }
+
+    #[test]
+    fn test_check_duplicate_output_files_through_parent_dir() {
+        let base_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(base_dir.path().join("proofs")).unwrap();
+        let jobs: Vec<_> = parse_jobs(
+            r#"
+            [[jobs]]
+            name = "a"
+            programs = ["a.json"]
+            output_file = "proof.json"
+
+            [[jobs]]
+            name = "b"
+            programs = ["b.json"]
+            output_file = "proofs/../proof.json"
+            "#,
+        )
+        .iter()
+        .map(|job| {
+            let name = job.name(0);
+            let command = job.command(&name, base_dir.path());
+            (name, command)
+        })
+        .collect();
+        assert!(matches!(
+            check_duplicate_jobs(&jobs),
+            Err(BatchError::DuplicateOutputFile(_))
+        ));
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(1)), Ok(1));
+        assert_eq!(
+            catch_panic::<()>(|| Err("failed".to_string())),
+            Err("failed".to_string())
+        );
+        assert_eq!(
+            catch_panic::<()>(|| panic!("index out of bounds")),
+            Err("panicked: index out of bounds".to_string())
+        );
+        assert_eq!(
+            catch_panic::<()>(|| panic!("{} out of bounds", "index")),
+            Err("panicked: index out of bounds".to_string())
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use std::path::Path;
+
+use log::info;
+use serde::Serialize;
+use stone_prover_sdk::fri::generate_prover_parameters;
+use stone_prover_sdk::models::{ProverConfig, Verifier};
+
+use crate::cli::InitConfigArgs;
+use crate::commands::params::{get_n_steps, ParamsError};
+use crate::toolkit::json::write_pretty_json_to_file;
+
+pub const PROVER_CONFIG_FILE: &str = "cpu_air_prover_config.json";
+pub const PARAMETER_FILE: &str = "cpu_air_params.json";
+
+const PROVER_CONFIG_FIELDS: &[(&str, &str)] = &[
+    (
+        "/constraint_polynomial_task_size",
+        "Number of rows evaluated per task when computing the composition polynomial.",
+    ),
+    (
+        "/n_out_of_memory_merkle_layers",
+        "Number of Merkle tree layers recomputed instead of kept in memory. Trades time for memory.",
+    ),
+    (
+        "/table_prover_n_tasks_per_segment",
+        "Number of tasks per trace segment when committing to the trace.",
+    ),
+    (
+        "/cached_lde_config/store_full_lde",
+        "Keep the full low-degree extension of the trace in memory. Faster, but memory hungry.",
+    ),
+    (
+        "/cached_lde_config/use_fft_for_eval",
+        "Use FFTs to evaluate the trace on the evaluation domain.",
+    ),
+];
+
+const PARAMETER_FIELDS: &[(&str, &str)] = &[
+    (
+        "/field",
+        "Field of the AIR. Cairo programs are always proved over PrimeField0.",
+    ),
+    (
+        "/use_extension_field",
+        "Use an extension field for the out-of-domain sampling. Not supported for Cairo.",
+    ),
+    (
+        "/stark/log_n_cosets",
+        "Log2 of the blowup factor. Each query provides log_n_cosets bits of security.",
+    ),
+    (
+        "/stark/fri/fri_step_list",
+        "Number of layers folded at each FRI step. sum(fri_step_list) + log2(last_layer_degree_bound) must equal log2(n_steps) + 4.",
+    ),
+    (
+        "/stark/fri/last_layer_degree_bound",
+        "Degree bound of the last FRI layer, sent in the clear.",
+    ),
+    (
+        "/stark/fri/n_queries",
+        "Number of FRI queries. Security is n_queries * log_n_cosets + proof_of_work_bits bits.",
+    ),
+    (
+        "/stark/fri/proof_of_work_bits",
+        "Number of bits of proof of work required before sampling the queries.",
+    ),
+];
+
+/// Writes `obj` to `path` and prints the value and purpose of each documented field.
+fn write_documented_file<T: Serialize>(
+    obj: &T,
+    path: &Path,
+    fields: &[(&str, &str)],
+) -> Result<(), ParamsError> {
+    let value = serde_json::to_value(obj)?;
+    let documented_fields = fields
+        .iter()
+        .map(|(pointer, description)| {
+            value
+                .pointer(pointer)
+                .map(|field_value| (*pointer, field_value, *description))
+                .ok_or_else(|| ParamsError::UndocumentedField(pointer.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    write_pretty_json_to_file(obj, path).map_err(|e| ParamsError::Io(path.to_path_buf(), e))?;
+
+    println!("{}:", path.to_string_lossy());
+    for (pointer, field_value, description) in documented_fields {
+        println!("  {} = {field_value}", &pointer[1..].replace('/', "."));
+        println!("      {description}");
+    }
+    println!();
+
+    Ok(())
+}
+
+pub fn init_config(args: InitConfigArgs) -> Result<(), ParamsError> {
+    let output_dir = args.output_dir.as_deref().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| ParamsError::Io(output_dir.to_path_buf(), e))?;
+
+    let prover_config_file = output_dir.join(PROVER_CONFIG_FILE);
+    let parameter_file = output_dir.join(PARAMETER_FILE);
+    if !args.force {
+        for path in [&prover_config_file, &parameter_file] {
+            if path.exists() {
+                return Err(ParamsError::FileExists(path.clone()));
+            }
+        }
+    }
+
+    let n_steps = get_n_steps(&args.params)?;
+    let verifier = args.params.verifier.unwrap_or(Verifier::Stone);
+
+    write_documented_file(
+        &ProverConfig::default(),
+        &prover_config_file,
+        PROVER_CONFIG_FIELDS,
+    )?;
+    write_documented_file(
+        &generate_prover_parameters(n_steps, verifier),
+        &parameter_file,
+        PARAMETER_FIELDS,
+    )?;
+
+    info!(
+        "use them with `stone prove --prover-config-file {} --parameter-file {}`.",
+        prover_config_file.to_string_lossy(),
+        parameter_file.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_documented_fields_exist() {
+        let output_dir = tempfile::tempdir().unwrap();
+        write_documented_file(
+            &ProverConfig::default(),
+            &output_dir.path().join(PROVER_CONFIG_FILE),
+            PROVER_CONFIG_FIELDS,
+        )
+        .unwrap();
+        write_documented_file(
+            &generate_prover_parameters(1 << 15, Verifier::Stone),
+            &output_dir.path().join(PARAMETER_FILE),
+            PARAMETER_FIELDS,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_write_documented_file_missing_field() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let path = output_dir.path().join(PROVER_CONFIG_FILE);
+        let result = write_documented_file(
+            &ProverConfig::default(),
+            &path,
+            &[("/store_full_lde", "Not a top-level field.")],
+        );
+
+        assert!(
+            matches!(result, Err(ParamsError::UndocumentedField(pointer)) if pointer == "/store_full_lde")
+        );
+        assert!(!path.exists());
+    }
+}
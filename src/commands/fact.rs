@@ -0,0 +1,173 @@
+use std::path::{Path, PathBuf};
+
+use cairo_vm::Felt252;
+use log::warn;
+use serde::Serialize;
+use stone_prover_sdk::models::Proof;
+
+use crate::cli::{FactCommand, FactSource, HashFunction, OutputFormat};
+use crate::toolkit::fact::{
+    compute_fact, compute_output_root, compute_program_hash, parse_bootloader_output, to_hex,
+    CompiledProgram, FactError, FactTopologiesFile, FactTopology,
+};
+use crate::toolkit::felt::{load_felt_list, LoadFeltListError};
+use crate::toolkit::json::{read_json_from_file, ReadJsonError};
+use crate::toolkit::output::{read_program_output, OutputError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum FactCommandError {
+    #[error("Failed to deserialize {0}: {1}")]
+    Deserialize(PathBuf, ReadJsonError),
+
+    #[error(transparent)]
+    InvalidOutput(#[from] LoadFeltListError),
+
+    #[error(transparent)]
+    Output(#[from] OutputError),
+
+    #[error("Invalid bootloader output: {0}")]
+    InvalidBootloaderOutput(String),
+
+    #[error("Expected {expected} fact topologies, found {actual}")]
+    FactTopologiesMismatch { expected: usize, actual: usize },
+
+    #[error(transparent)]
+    Fact(#[from] FactError),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+}
+
+#[derive(Serialize, Debug)]
+struct FactInfo {
+    program_hash: String,
+    output_size: usize,
+    fact: String,
+}
+
+impl FactInfo {
+    fn new(
+        program_hash: &Felt252,
+        output: &[Felt252],
+        fact_topology: &FactTopology,
+    ) -> Result<Self, FactError> {
+        let output_root = compute_output_root(output, fact_topology)?;
+        Ok(Self {
+            program_hash: to_hex(&program_hash.to_bytes_be()),
+            output_size: output.len(),
+            fact: to_hex(&compute_fact(program_hash, &output_root)),
+        })
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, FactCommandError> {
+    read_json_from_file(path).map_err(|e| FactCommandError::Deserialize(path.to_path_buf(), e))
+}
+
+fn read_proof_output(proof_file: &Path) -> Result<Vec<Felt252>, FactCommandError> {
+    let proof: Proof = read_json(proof_file)?;
+    Ok(read_program_output(&proof.public_input)?)
+}
+
+fn program_facts(
+    program_file: &Path,
+    proof_file: Option<&Path>,
+    output: Option<&str>,
+    hash_function: HashFunction,
+    entrypoint: &str,
+) -> Result<Vec<FactInfo>, FactCommandError> {
+    let program: CompiledProgram = read_json(program_file)?;
+    let program_hash = compute_program_hash(&program, hash_function, entrypoint)?;
+
+    let output = match (proof_file, output) {
+        (Some(proof_file), _) => read_proof_output(proof_file)?,
+        (None, Some(output)) => load_felt_list(output)?,
+        (None, None) => unreachable!("validated by the CLI"),
+    };
+
+    let fact_info = FactInfo::new(&program_hash, &output, &FactTopology::trivial(output.len()))?;
+    Ok(vec![fact_info])
+}
+
+fn bootloader_facts(
+    proof_file: &Path,
+    fact_topologies_file: Option<&Path>,
+) -> Result<Vec<FactInfo>, FactCommandError> {
+    let output = read_proof_output(proof_file)?;
+    let task_outputs =
+        parse_bootloader_output(&output).map_err(FactCommandError::InvalidBootloaderOutput)?;
+
+    let fact_topologies = match fact_topologies_file {
+        Some(path) => {
+            let fact_topologies: FactTopologiesFile = read_json(path)?;
+            fact_topologies.fact_topologies
+        }
+        None => {
+            warn!("no fact topologies file specified, assuming each task output is a single page.");
+            task_outputs
+                .iter()
+                .map(|task_output| FactTopology::trivial(task_output.output.len()))
+                .collect()
+        }
+    };
+
+    if fact_topologies.len() != task_outputs.len() {
+        return Err(FactCommandError::FactTopologiesMismatch {
+            expected: task_outputs.len(),
+            actual: fact_topologies.len(),
+        });
+    }
+
+    let facts = task_outputs
+        .iter()
+        .zip(fact_topologies.iter())
+        .map(|(task_output, fact_topology)| {
+            FactInfo::new(
+                &task_output.program_hash,
+                &task_output.output,
+                fact_topology,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(facts)
+}
+
+pub fn fact(command: FactCommand) -> Result<(), FactCommandError> {
+    let facts = match &command.source {
+        FactSource::Program {
+            program_file,
+            proof_file,
+            output,
+            hash_function,
+            entrypoint,
+        } => program_facts(
+            program_file,
+            proof_file.as_deref(),
+            output.as_deref(),
+            *hash_function,
+            entrypoint,
+        )?,
+        FactSource::Bootloader {
+            proof_file,
+            fact_topologies_file,
+        } => bootloader_facts(proof_file, fact_topologies_file.as_deref())?,
+    };
+
+    match command.format {
+        OutputFormat::Text => {
+            let with_bootloader = matches!(command.source, FactSource::Bootloader { .. });
+            let indent = if with_bootloader { "  " } else { "" };
+            for (index, fact_info) in facts.iter().enumerate() {
+                if with_bootloader {
+                    println!("Task {index}:");
+                }
+                println!("{indent}program hash: {}", fact_info.program_hash);
+                println!("{indent}output size:  {}", fact_info.output_size);
+                println!("{indent}fact:         {}", fact_info.fact);
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&facts)?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use stone_prover_sdk::models::{FriParameters, Layout, Proof, ProverConfig};
+
+use crate::cli::{InspectArgs, OutputFormat};
+use crate::toolkit::json::{read_json_from_file, ReadJsonError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum InspectError {
+    #[error("Failed to deserialize {0}: {1}")]
+    Deserialize(PathBuf, ReadJsonError),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+}
+
+#[derive(Serialize, Debug)]
+struct MemorySegmentSummary {
+    begin_addr: u64,
+    stop_ptr: u64,
+    size: u64,
+}
+
+#[derive(Serialize, Debug)]
+struct ProofSummary<'a> {
+    layout: &'a Layout,
+    n_steps: u64,
+    rc_min: u64,
+    rc_max: u64,
+    memory_segments: BTreeMap<&'a str, MemorySegmentSummary>,
+    public_memory_size: usize,
+    field: &'a str,
+    use_extension_field: bool,
+    log_n_cosets: u64,
+    fri: &'a FriParameters,
+    /// Conjectured security, in bits: `n_queries * log_n_cosets + proof_of_work_bits`.
+    security_bits: u64,
+    prover_config: &'a ProverConfig,
+    proof_size_bytes: usize,
+}
+
+impl<'a> ProofSummary<'a> {
+    fn new(proof: &'a Proof) -> Self {
+        let public_input = &proof.public_input;
+        let stark = &proof.proof_parameters.stark;
+
+        let memory_segments = public_input
+            .memory_segments
+            .iter()
+            .map(|(name, segment)| {
+                let begin_addr = segment.begin_addr as u64;
+                let stop_ptr = segment.stop_ptr as u64;
+                let summary = MemorySegmentSummary {
+                    begin_addr,
+                    stop_ptr,
+                    size: stop_ptr.saturating_sub(begin_addr),
+                };
+                (name.as_str(), summary)
+            })
+            .collect();
+
+        let proof_hex = proof.proof_hex.trim_start_matches("0x");
+        let security_bits = stark.fri.n_queries as u64 * stark.log_n_cosets as u64
+            + stark.fri.proof_of_work_bits as u64;
+
+        Self {
+            layout: &public_input.layout,
+            n_steps: public_input.n_steps as u64,
+            rc_min: public_input.rc_min as u64,
+            rc_max: public_input.rc_max as u64,
+            memory_segments,
+            public_memory_size: public_input.public_memory.len(),
+            field: &proof.proof_parameters.field,
+            use_extension_field: proof.proof_parameters.use_extension_field,
+            log_n_cosets: stark.log_n_cosets as u64,
+            fri: &stark.fri,
+            security_bits,
+            prover_config: &proof.prover_config,
+            proof_size_bytes: proof_hex.len() / 2,
+        }
+    }
+
+    fn print(&self) -> Result<(), serde_json::Error> {
+        println!("Layout:              {}", self.layout);
+        println!("Steps:               {}", self.n_steps);
+        println!("Range check bounds:  [{}, {}]", self.rc_min, self.rc_max);
+        println!("Public memory cells: {}", self.public_memory_size);
+        println!("Proof size:          {} bytes", self.proof_size_bytes);
+        println!();
+
+        println!("Memory segments:");
+        for (name, segment) in &self.memory_segments {
+            println!(
+                "  {name:<20} [{}, {}) ({} cells)",
+                segment.begin_addr, segment.stop_ptr, segment.size
+            );
+        }
+        println!();
+
+        println!("Proof parameters:");
+        println!("  field:                   {}", self.field);
+        println!("  use_extension_field:     {}", self.use_extension_field);
+        println!("  log_n_cosets:            {}", self.log_n_cosets);
+        println!("  fri_step_list:           {:?}", self.fri.fri_step_list);
+        println!(
+            "  last_layer_degree_bound: {}",
+            self.fri.last_layer_degree_bound
+        );
+        println!("  n_queries:               {}", self.fri.n_queries);
+        println!("  proof_of_work_bits:      {}", self.fri.proof_of_work_bits);
+        println!("  security bits:           {}", self.security_bits);
+        println!();
+
+        println!("Prover config:");
+        if let serde_json::Value::Object(fields) = serde_json::to_value(self.prover_config)? {
+            for (name, value) in fields {
+                println!("  {name}: {value}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn inspect(args: InspectArgs) -> Result<(), InspectError> {
+    let proof: Proof = read_json_from_file(&args.proof_file)
+        .map_err(|e| InspectError::Deserialize(args.proof_file.clone(), e))?;
+    let summary = ProofSummary::new(&proof);
+
+    match args.format {
+        OutputFormat::Text => summary.print()?,
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summary)?),
+    }
+
+    Ok(())
+}
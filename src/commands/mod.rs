@@ -1,5 +1,19 @@
+pub mod batch;
+pub mod config;
+pub mod fact;
+pub mod inspect;
+pub mod params;
 pub mod prove;
+pub mod run;
+pub mod split_proof;
 pub mod verify;
 
+pub use batch::batch;
+pub use config::init_config;
+pub use fact::fact;
+pub use inspect::inspect;
+pub use params::generate_params;
 pub use prove::prove;
+pub use run::run;
+pub use split_proof::split_proof;
 pub use verify::verify;
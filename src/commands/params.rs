@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use log::{info, warn};
+use stone_prover_sdk::fri::generate_prover_parameters;
+use stone_prover_sdk::models::Verifier;
+
+use crate::cli::{GenerateParamsArgs, ParamsArgs};
+use crate::commands::prove::{run_program, ExecutionOutputs, RunError};
+use crate::toolkit::json::write_pretty_json_to_file;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParamsError {
+    #[error(transparent)]
+    Run(#[from] RunError),
+
+    #[error("Failed to write file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("{0} already exists, use --force to overwrite it")]
+    FileExists(PathBuf),
+
+    #[error("Cannot round {0} steps up to a power of two that fits in 32 bits")]
+    TooManySteps(u32),
+
+    #[error("Internal error: documented field {0} is missing from the file")]
+    UndocumentedField(String),
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Returns the number of steps of the trace, either specified by the user or obtained
+/// by running the program.
+pub fn get_n_steps(args: &ParamsArgs) -> Result<u32, ParamsError> {
+    if let Some(program) = &args.program {
+        info!("execution in progress...");
+        let execution_artifacts = run_program(
+            program.clone(),
+            args.program_input.as_deref(),
+            args.args.as_ref(),
+            None,
+            args.layout,
+            &args.verifier.unwrap_or(Verifier::Stone),
+            args.allow_missing_builtins,
+            None,
+            ExecutionOutputs::default(),
+        )?;
+        let n_steps = execution_artifacts.public_input.n_steps;
+        info!("execution completed in {n_steps} steps.");
+        return Ok(n_steps);
+    }
+
+    // Enforced by clap
+    let n_steps = args.n_steps.unwrap();
+    let trace_length = n_steps
+        .checked_next_power_of_two()
+        .ok_or(ParamsError::TooManySteps(n_steps))?;
+    if trace_length != n_steps {
+        warn!(
+            "the number of steps must be a power of two, rounding {n_steps} up to {trace_length}."
+        );
+    }
+    Ok(trace_length)
+}
+
+pub fn generate_params(args: GenerateParamsArgs) -> Result<(), ParamsError> {
+    let n_steps = get_n_steps(&args.params)?;
+    let verifier = args.params.verifier.unwrap_or(Verifier::Stone);
+    let prover_parameters = generate_prover_parameters(n_steps, verifier);
+
+    let output_file = args.output_file();
+    write_pretty_json_to_file(&prover_parameters, output_file.as_ref())
+        .map_err(|e| ParamsError::Io(output_file.to_path_buf(), e))?;
+    info!(
+        "prover parameters for {n_steps} steps written to {}.",
+        output_file.to_string_lossy()
+    );
+
+    Ok(())
+}
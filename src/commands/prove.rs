@@ -1,25 +1,53 @@
 use std::borrow::Cow;
-use std::fs::File;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
+use cairo_vm::air_private_input::{AirPrivateInput, AirPrivateInputSerializable};
+use cairo_vm::cairo_run::{cairo_run_program_with_initial_scope, CairoRunConfig};
 use cairo_vm::hint_processor::builtin_hint_processor::bootloader::types::{Task, TaskSpec};
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
 use cairo_vm::types::errors::cairo_pie_error::CairoPieError;
 use cairo_vm::types::errors::program_errors::ProgramError;
+use cairo_vm::types::exec_scope::ExecutionScopes;
 use cairo_vm::types::program::Program;
+use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_vm::vm::runners::cairo_pie::CairoPie;
-use log::{debug, info};
-use serde::Serialize;
+use cairo_vm::vm::runners::cairo_runner::{CairoRunner, ResourceTracker, RunResources};
+use cairo_vm::vm::vm_core::VirtualMachine;
+use log::{debug, info, warn};
 use stone_prover_sdk::cairo_vm::{
-    extract_execution_artifacts, run_bootloader_in_proof_mode, run_in_proof_mode,
-    ExecutionArtifacts, ExecutionError,
+    extract_execution_artifacts, run_bootloader_in_proof_mode, ExecutionArtifacts, ExecutionError,
 };
 use stone_prover_sdk::error::ProverError;
 use stone_prover_sdk::fri::generate_prover_parameters;
-use stone_prover_sdk::models::{Layout, ProverConfig};
+use stone_prover_sdk::models::{Layout, ProverConfig, ProverParameters, PublicInput, Verifier};
 use stone_prover_sdk::prover::run_prover;
 
-use crate::cli::{Bootloader, Executable, ProveCommand};
-use crate::toolkit::json::{read_json_from_file, ReadJsonError};
+use crate::cli::{
+    Bootloader, Executable, ExecutionArtifactsPaths, ExecutionLimits, OutputFormat,
+    ProgramOutputArgs, ProveCommand,
+};
+use crate::commands::config::{PARAMETER_FILE, PROVER_CONFIG_FILE};
+use crate::commands::run::{write_cairo_run_files, write_execution_artifacts};
+use crate::toolkit::cairo_pie::zip_cairo_pie_directory;
+use crate::toolkit::entrypoint::{set_proof_mode_entrypoint, EntrypointError};
+use crate::toolkit::file_type::{detect_task_file_type, FileTypeError, TaskFileType};
+use crate::toolkit::json::{
+    read_json_from_file, write_json_to_file, write_pretty_json_to_file, ReadJsonError,
+};
+use crate::toolkit::layout::{select_layout, ResourceUsage, PROBE_LAYOUT};
+use crate::toolkit::output::{OutputError, OutputValues};
+use crate::toolkit::profile::{ExecutionProfile, ProfileError};
+use crate::toolkit::program_cache::{cached_program, load_program};
+use crate::toolkit::program_input::{LoadedProgramInput, ProgramInputError};
+use crate::toolkit::resources::{ExecutionReport, MEMORY_CELL_SIZE};
+use crate::toolkit::sierra::{
+    load_sierra_program, run_sierra_program, sierra_to_cairo_pie, FunctionArgs, SierraError,
+};
+use crate::toolkit::tasks::{
+    read_bootloader_input, read_task_manifest, BootloaderTask, ProgramInput, TaskManifestError,
+    TaskProgram,
+};
 
 const BOOTLOADER_V0_12_3: &[u8] =
     include_bytes!("../../dependencies/cairo-programs/bootloader/bootloader-v0.12.3.json");
@@ -27,26 +55,22 @@ const BOOTLOADER_V0_12_3: &[u8] =
 const BOOTLOADER_V0_13_0: &[u8] =
     include_bytes!("../../dependencies/cairo-programs/bootloader/bootloader-v0.13.0.json");
 
-fn write_json_to_file<T: Serialize, P: AsRef<Path>>(obj: T, path: P) -> Result<(), std::io::Error> {
-    let mut file = File::create(path)?;
-    serde_json::to_writer(&mut file, &obj)?;
-    Ok(())
-}
+/// Function called to run a program, unless specified otherwise.
+pub const DEFAULT_ENTRYPOINT: &str = "main";
 
 fn load_bootloader(bootloader: Bootloader) -> Result<Program, RunError> {
     let bootloader_bytes = match bootloader {
-        Bootloader::V0_12_3 => Cow::Borrowed(BOOTLOADER_V0_12_3),
-        Bootloader::V0_13_0 => Cow::Borrowed(BOOTLOADER_V0_13_0),
+        Bootloader::V0_12_3 => BOOTLOADER_V0_12_3,
+        Bootloader::V0_13_0 => BOOTLOADER_V0_13_0,
         Bootloader::Custom(path) => {
-            let content = std::fs::read(&path).map_err(|e| RunError::Io(path, e))?;
-            Cow::Owned(content)
+            return load_program(&path, "main").map_err(|e| RunError::FailedToLoadProgram(path, e));
         }
     };
 
-    let bootloader_program = Program::from_bytes(bootloader_bytes.as_ref(), Some("main"))
-        .map_err(RunError::FailedToLoadBootloader)?;
-
-    Ok(bootloader_program)
+    cached_program(&format!("{bootloader:?}"), "main", || {
+        Program::from_bytes(bootloader_bytes, Some("main"))
+    })
+    .map_err(RunError::FailedToLoadBootloader)
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -54,6 +78,9 @@ pub enum RunError {
     #[error("Failed to read file {0}: {1}")]
     Io(PathBuf, std::io::Error),
 
+    #[error("Failed to write file {0}: {1}")]
+    Write(PathBuf, std::io::Error),
+
     #[error("Failed to deserialize {0}: {1}")]
     Deserialize(PathBuf, ReadJsonError),
 
@@ -66,71 +93,676 @@ pub enum RunError {
     #[error("Failed to load PIE {0}: {1}")]
     FailedToLoadPie(PathBuf, CairoPieError),
 
+    #[error("Failed to detect the type of task {0}: {1}")]
+    UnknownTaskType(PathBuf, FileTypeError),
+
+    #[error("Task {0} is a {1}, which is not supported")]
+    UnsupportedTaskType(PathBuf, TaskFileType),
+
+    #[error("Failed to read task file {0}: {1}")]
+    InvalidTaskManifest(PathBuf, TaskManifestError),
+
+    #[error("Invalid program input for {0}: {1}")]
+    ProgramInput(PathBuf, ProgramInputError),
+
+    #[error("Cannot provide a program input for Cairo PIE {0}")]
+    ProgramInputForPie(PathBuf),
+
+    #[error("Cannot provide a program input for Sierra program {0}")]
+    ProgramInputForSierra(PathBuf),
+
+    #[error("Cannot provide function arguments for Cairo 0 program {0}")]
+    FunctionArgsForCairo0(PathBuf),
+
+    #[error("Cannot set the entrypoint of Sierra program {0}")]
+    EntrypointForSierra(PathBuf),
+
+    #[error("Cannot allow missing builtins for Sierra program {0}")]
+    MissingBuiltinsForSierra(PathBuf),
+
+    #[error("Cannot limit the steps of Sierra program {0}")]
+    MaxStepsForSierra(PathBuf),
+
+    #[error("Cannot run {0} from entrypoint {1}: {2}")]
+    InvalidEntrypoint(PathBuf, String, EntrypointError),
+
+    #[error("Cannot run CASM program {0}")]
+    CasmProgram(PathBuf),
+
+    #[error("Failed to run Sierra program {0}: {1}")]
+    Sierra(PathBuf, SierraError),
+
+    #[error("Cannot profile Sierra program {0}")]
+    ProfileNotSupported(PathBuf),
+
+    #[error("Failed to write the execution profile: {0}")]
+    Profile(#[from] ProfileError),
+
+    #[error("Execution exceeded the limit of {limit} {resource}")]
+    LimitExceeded {
+        resource: &'static str,
+        limit: usize,
+    },
+
+    #[error("The dynamic layout is not supported")]
+    DynamicLayoutNotSupported,
+
+    #[error("Failed to read the program output: {0}")]
+    ProgramOutput(#[from] OutputError),
+
     #[error(transparent)]
     FailedExecution(#[from] ExecutionError),
 
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+
     #[error(transparent)]
     Prover(#[from] ProverError),
 }
 
-pub fn run_program(
-    program_path: PathBuf,
+/// Runs the program, running the hints that read its program input. The execution is
+/// aborted after `max_steps` steps, if specified.
+fn run_with_program_input(
+    program: &Program,
+    program_input: Option<&LoadedProgramInput>,
+    cairo_run_config: &CairoRunConfig,
+    max_steps: Option<usize>,
+) -> Result<(CairoRunner, VirtualMachine), RunError> {
+    let extra_hints = program_input
+        .map(|program_input| program_input.hints.clone())
+        .unwrap_or_default();
+    let run_resources = max_steps.map(RunResources::new).unwrap_or_default();
+    let mut hint_processor = BuiltinHintProcessor::new(extra_hints, run_resources);
+    cairo_run_program_with_initial_scope(
+        program,
+        cairo_run_config,
+        &mut hint_processor,
+        ExecutionScopes::new(),
+    )
+    .map_err(|e| match max_steps {
+        // The VM stops before the end of the program once all the steps are consumed
+        Some(limit) if hint_processor.consumed() => RunError::LimitExceeded {
+            resource: "steps",
+            limit,
+        },
+        _ => ExecutionError::RunFailed(e).into(),
+    })
+}
+
+/// Aborts if the execution uses more memory cells than allowed.
+fn check_memory_cells(
+    execution_artifacts: &ExecutionArtifacts,
+    max_memory_cells: Option<usize>,
+) -> Result<(), RunError> {
+    let Some(max_memory_cells) = max_memory_cells else {
+        return Ok(());
+    };
+    let n_memory_cells = execution_artifacts.memory.len() / MEMORY_CELL_SIZE;
+    debug!("the execution uses {n_memory_cells} memory cells.");
+    if n_memory_cells > max_memory_cells {
+        return Err(RunError::LimitExceeded {
+            resource: "memory cells",
+            limit: max_memory_cells,
+        });
+    }
+
+    Ok(())
+}
+
+fn read_program_input(path: &Path) -> Result<serde_json::Value, RunError> {
+    read_json_from_file(path).map_err(|e| RunError::Deserialize(path.to_path_buf(), e))
+}
+
+/// Reads a compiled Cairo 0 program as JSON, to look up its hints.
+fn read_program_json(path: &Path) -> Result<serde_json::Value, RunError> {
+    read_json_from_file(path).map_err(|e| RunError::Deserialize(path.to_path_buf(), e))
+}
+
+/// Resolves the hints of a compiled Cairo 0 program that read its program input.
+fn load_program_input(
+    program_path: &Path,
+    program: &serde_json::Value,
+    program_input: serde_json::Value,
+) -> Result<LoadedProgramInput, RunError> {
+    LoadedProgramInput::new(program_input, program)
+        .map_err(|e| RunError::ProgramInput(program_path.to_path_buf(), e))
+}
+
+/// Runs the program outside of proof mode and returns the execution as a Cairo PIE.
+///
+/// Cairo PIEs cannot be generated from a proof mode run, hence the separate execution.
+fn run_to_cairo_pie(
+    program: &Program,
+    program_input: Option<&LoadedProgramInput>,
+    entrypoint: &str,
     layout: Layout,
     allow_missing_builtins: bool,
+    max_steps: Option<usize>,
+) -> Result<CairoPie, RunError> {
+    let layout_str = layout.to_string();
+    let cairo_run_config = CairoRunConfig {
+        entrypoint,
+        trace_enabled: false,
+        relocate_mem: false,
+        layout: &layout_str,
+        proof_mode: false,
+        secure_run: None,
+        disable_trace_padding: false,
+        allow_missing_builtins: Some(allow_missing_builtins),
+    };
+    let (runner, vm) =
+        run_with_program_input(program, program_input, &cairo_run_config, max_steps)?;
+    let cairo_pie = runner
+        .get_cairo_pie(&vm)
+        .map_err(|e| ExecutionError::RunFailed(CairoRunError::Runner(e)))?;
+
+    Ok(cairo_pie)
+}
+
+/// Builtins used by the bootloader itself to write the outputs and hash the programs
+/// of the tasks.
+const BOOTLOADER_BUILTINS: [&str; 3] = ["output", "pedersen", "range_check"];
+
+/// Returns the cheapest layout that supports the builtins, fits the execution and can be
+/// checked by the verifier, and logs why it was chosen.
+fn choose_layout(
+    usage: &ResourceUsage,
+    required_builtins: &BTreeSet<String>,
+    verifier: &Verifier,
+) -> Layout {
+    let builtins = match required_builtins.is_empty() {
+        true => "no builtins".to_string(),
+        false => format!(
+            "builtins {}",
+            required_builtins
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+
+    match select_layout(usage, required_builtins, verifier) {
+        Some(selection) => {
+            info!(
+                "selected layout {}: cheapest layout with {builtins} that fits {} steps in a trace of {} steps ({} cells).",
+                selection.layout, usage.n_steps, selection.trace_length, selection.trace_cells
+            );
+            selection.layout
+        }
+        None => {
+            warn!("no layout supports {builtins} for the {verifier:?} verifier, falling back to {PROBE_LAYOUT}.");
+            PROBE_LAYOUT
+        }
+    }
+}
+
+/// Runs the program once outside of proof mode to measure its resources, then selects
+/// its layout. Also returns the Cairo PIE of the run.
+fn select_program_layout(
+    program: &Program,
+    program_input: Option<&LoadedProgramInput>,
+    entrypoint: &str,
+    allow_missing_builtins: bool,
+    max_steps: Option<usize>,
+    verifier: &Verifier,
+) -> Result<(Layout, CairoPie), RunError> {
+    debug!("measuring execution resources to select the layout...");
+    let cairo_pie = run_to_cairo_pie(
+        program,
+        program_input,
+        entrypoint,
+        PROBE_LAYOUT,
+        allow_missing_builtins,
+        max_steps,
+    )?;
+    let usage = ResourceUsage::from_execution_resources(&cairo_pie.execution_resources);
+    let layout = choose_layout(
+        &usage,
+        &usage.required_builtins(allow_missing_builtins),
+        verifier,
+    );
+
+    Ok((layout, cairo_pie))
+}
+
+/// Measures the resources of the tasks, running program tasks outside of proof mode.
+/// Each program task is aborted after `max_steps` steps, and the tasks must not exceed
+/// `max_steps` steps in total either.
+fn measure_tasks(tasks: &[TaskSpec], max_steps: Option<usize>) -> Result<ResourceUsage, RunError> {
+    debug!("measuring execution resources of the tasks...");
+    let mut usage = ResourceUsage::default();
+    for task in tasks {
+        let task_usage = match &task.task {
+            Task::Pie(cairo_pie) => {
+                ResourceUsage::from_execution_resources(&cairo_pie.execution_resources)
+            }
+            Task::Program(program) => {
+                let cairo_pie = run_to_cairo_pie(
+                    program,
+                    None,
+                    DEFAULT_ENTRYPOINT,
+                    PROBE_LAYOUT,
+                    true,
+                    max_steps,
+                )?;
+                ResourceUsage::from_execution_resources(&cairo_pie.execution_resources)
+            }
+        };
+        usage.add(&task_usage);
+    }
+
+    if let Some(max_steps) = max_steps {
+        if usage.n_steps > max_steps {
+            return Err(RunError::LimitExceeded {
+                resource: "steps",
+                limit: max_steps,
+            });
+        }
+    }
+
+    Ok(usage)
+}
+
+/// Selects the layout of a bootloader run from the resources of its tasks. The steps
+/// of the bootloader itself are not accounted for.
+fn select_bootloader_layout(usage: &ResourceUsage, verifier: &Verifier) -> Layout {
+    let mut required_builtins = usage.required_builtins(true);
+    required_builtins.extend(BOOTLOADER_BUILTINS.map(String::from));
+
+    choose_layout(usage, &required_builtins, verifier)
+}
+
+/// Rejects the layouts that the execution cannot be proved with.
+///
+/// Stone reads the column assignment of the dynamic AIR from the public input, which the
+/// CLI cannot generate, and the Cairo VM allocates the builtins of the dynamic layout
+/// without ratios.
+fn check_layout(layout: Option<Layout>) -> Result<(), RunError> {
+    match layout {
+        Some(Layout::Dynamic) => Err(RunError::DynamicLayoutNotSupported),
+        _ => Ok(()),
+    }
+}
+
+/// Runs the `main` function of a Sierra program in proof mode.
+///
+/// Unlike Cairo 0 programs, Cairo 1 programs cannot be interrupted, so their steps cannot
+/// be limited. Their gas already bounds the execution. The layout must provide all the builtins of the program, as the Cairo 1 runner
+/// initializes them all.
+fn run_sierra(
+    program_path: &Path,
+    args: Option<&FunctionArgs>,
+    layout: Option<Layout>,
+    verifier: &Verifier,
+    outputs: ExecutionOutputs,
 ) -> Result<ExecutionArtifacts, RunError> {
-    let program =
-        std::fs::read(program_path.as_path()).map_err(|e| RunError::Io(program_path, e))?;
-    let (runner, vm) = run_in_proof_mode(&program, layout, Some(allow_missing_builtins))
-        .map_err(ExecutionError::RunFailed)?;
-    extract_execution_artifacts(runner, vm).map_err(|e| e.into())
+    if outputs.profile.is_some() {
+        return Err(RunError::ProfileNotSupported(program_path.to_path_buf()));
+    }
+    let sierra_error = |e| RunError::Sierra(program_path.to_path_buf(), e);
+    let program = load_sierra_program(program_path).map_err(sierra_error)?;
+    let (layout, probe_cairo_pie) = match layout {
+        Some(layout) => (layout, None),
+        None => {
+            debug!("measuring execution resources to select the layout...");
+            let cairo_pie =
+                sierra_to_cairo_pie(&program, args, PROBE_LAYOUT).map_err(sierra_error)?;
+            let mut usage = ResourceUsage::from_execution_resources(&cairo_pie.execution_resources);
+            // The segment arena is not a layout builtin, it lives in regular memory
+            usage.builtin_instances.remove("segment_arena");
+            let layout = choose_layout(&usage, &usage.required_builtins(false), verifier);
+            (layout, Some(cairo_pie))
+        }
+    };
+
+    let (runner, vm) = run_sierra_program(&program, args, layout, true).map_err(sierra_error)?;
+    let execution_artifacts = extract_execution_artifacts(runner, vm)?;
+
+    if let Some(output_path) = outputs.cairo_pie {
+        debug!("exporting Cairo PIE...");
+        // The layout of the run is not part of the Cairo PIE, reuse the run that selected it
+        let cairo_pie = match probe_cairo_pie {
+            Some(cairo_pie) => cairo_pie,
+            None => sierra_to_cairo_pie(&program, args, layout).map_err(sierra_error)?,
+        };
+        cairo_pie
+            .write_zip_file(output_path)
+            .map_err(|e| RunError::Write(output_path.to_path_buf(), e))?;
+    }
+
+    Ok(execution_artifacts)
 }
 
-fn is_zip_file(file: &Path) -> bool {
-    match file.extension() {
-        Some(extension) => extension == "zip",
-        None => false,
+/// Files written from the execution of a program, besides the execution artifacts.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExecutionOutputs<'a> {
+    pub cairo_pie: Option<&'a Path>,
+    /// Folded stacks of the steps spent in each function.
+    pub profile: Option<&'a Path>,
+}
+
+/// Loads a compiled Cairo 0 program to run it in proof mode from `entrypoint`.
+///
+/// Proof mode always starts at `__start__`, which calls `main`: for other entrypoints,
+/// the call is redirected before loading the program.
+fn load_proof_mode_program(
+    program_path: &Path,
+    program_json: Option<&serde_json::Value>,
+    entrypoint: &str,
+) -> Result<Program, RunError> {
+    let load_error = |e| RunError::FailedToLoadProgram(program_path.to_path_buf(), e);
+    if entrypoint == DEFAULT_ENTRYPOINT {
+        return load_program(program_path, entrypoint).map_err(load_error);
+    }
+
+    let mut program_json = match program_json {
+        Some(program_json) => program_json.clone(),
+        None => read_program_json(program_path)?,
+    };
+    set_proof_mode_entrypoint(&mut program_json, entrypoint).map_err(|e| {
+        RunError::InvalidEntrypoint(program_path.to_path_buf(), entrypoint.to_string(), e)
+    })?;
+    // Keyed apart from the unmodified program, which bootloader tasks use
+    let source = format!("{} (proof mode)", program_path.to_string_lossy());
+    cached_program(&source, entrypoint, || {
+        Program::from_bytes(program_json.to_string().as_bytes(), Some(entrypoint))
+    })
+    .map_err(load_error)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run_program(
+    program_path: PathBuf,
+    program_input_path: Option<&Path>,
+    args: Option<&FunctionArgs>,
+    entrypoint: Option<&str>,
+    layout: Option<Layout>,
+    verifier: &Verifier,
+    allow_missing_builtins: bool,
+    max_steps: Option<usize>,
+    outputs: ExecutionOutputs,
+) -> Result<ExecutionArtifacts, RunError> {
+    check_layout(layout)?;
+    // Other files are reported as invalid when loading them as Cairo 0 programs
+    match detect_task_file_type(&program_path) {
+        Ok(TaskFileType::Sierra) => {
+            if program_input_path.is_some() {
+                return Err(RunError::ProgramInputForSierra(program_path));
+            }
+            if entrypoint.is_some() {
+                return Err(RunError::EntrypointForSierra(program_path));
+            }
+            if allow_missing_builtins {
+                return Err(RunError::MissingBuiltinsForSierra(program_path));
+            }
+            if max_steps.is_some() {
+                return Err(RunError::MaxStepsForSierra(program_path));
+            }
+            return run_sierra(&program_path, args, layout, verifier, outputs);
+        }
+        Ok(TaskFileType::Casm) => return Err(RunError::CasmProgram(program_path)),
+        _ => {}
+    }
+    if args.is_some() {
+        return Err(RunError::FunctionArgsForCairo0(program_path));
+    }
+
+    let entrypoint = entrypoint.unwrap_or(DEFAULT_ENTRYPOINT);
+    let program_json = program_input_path
+        .map(|_| read_program_json(&program_path))
+        .transpose()?;
+    let program_input = match (program_input_path, &program_json) {
+        (Some(path), Some(program_json)) => Some(load_program_input(
+            &program_path,
+            program_json,
+            read_program_input(path)?,
+        )?),
+        _ => None,
+    };
+    let program = load_proof_mode_program(&program_path, program_json.as_ref(), entrypoint)?;
+    let (layout, probe_cairo_pie) = match layout {
+        Some(layout) => (layout, None),
+        None => {
+            let (layout, cairo_pie) = select_program_layout(
+                &program,
+                program_input.as_ref(),
+                entrypoint,
+                allow_missing_builtins,
+                max_steps,
+                verifier,
+            )?;
+            (layout, Some(cairo_pie))
+        }
+    };
+
+    let layout_str = layout.to_string();
+    let cairo_run_config = CairoRunConfig {
+        entrypoint,
+        trace_enabled: true,
+        relocate_mem: true,
+        layout: &layout_str,
+        proof_mode: true,
+        secure_run: None,
+        disable_trace_padding: false,
+        allow_missing_builtins: Some(allow_missing_builtins),
+    };
+    let (runner, vm) = run_with_program_input(
+        &program,
+        program_input.as_ref(),
+        &cairo_run_config,
+        max_steps,
+    )?;
+    let execution_artifacts = extract_execution_artifacts(runner, vm)?;
+
+    if let Some(profile_path) = outputs.profile {
+        let profile = ExecutionProfile::new(&program, &execution_artifacts)?;
+        let files = profile
+            .write_folded(profile_path)
+            .map_err(|e| RunError::Write(profile_path.to_path_buf(), e))?;
+        info!(
+            "execution profile written to {}.",
+            files
+                .iter()
+                .map(|file| file.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
     }
+
+    if let Some(output_path) = outputs.cairo_pie {
+        debug!("exporting Cairo PIE...");
+        // The layout of the run is not part of the Cairo PIE, reuse the run that selected it
+        let cairo_pie = match probe_cairo_pie {
+            Some(cairo_pie) => cairo_pie,
+            None => run_to_cairo_pie(
+                &program,
+                program_input.as_ref(),
+                entrypoint,
+                layout,
+                allow_missing_builtins,
+                None,
+            )?,
+        };
+        cairo_pie
+            .write_zip_file(output_path)
+            .map_err(|e| RunError::Write(output_path.to_path_buf(), e))?;
+    }
+
+    Ok(execution_artifacts)
 }
 
 #[derive(thiserror::Error, Debug)]
 enum TaskError {
+    #[error(transparent)]
+    FileType(#[from] FileTypeError),
+
+    #[error("{0} tasks are not supported")]
+    Unsupported(TaskFileType),
+
     #[error(transparent)]
     Pie(#[from] CairoPieError),
 
+    #[error(transparent)]
+    PieDirectory(#[from] std::io::Error),
+
     #[error(transparent)]
     Program(#[from] ProgramError),
 }
 
-fn task_from_file(file: &Path) -> Result<TaskSpec, TaskError> {
-    let task = if is_zip_file(file) {
-        let pie = CairoPie::from_file(file)?;
-        Task::Pie(pie)
-    } else {
-        let program = Program::from_file(file, Some("main"))?;
-        Task::Program(program)
+impl TaskError {
+    fn into_run_error(self, path: &Path) -> RunError {
+        let path = path.to_path_buf();
+        match self {
+            TaskError::FileType(e) => RunError::UnknownTaskType(path, e),
+            TaskError::Unsupported(file_type) => RunError::UnsupportedTaskType(path, file_type),
+            TaskError::Pie(e) => RunError::FailedToLoadPie(path, e),
+            TaskError::PieDirectory(e) => RunError::Io(path, e),
+            TaskError::Program(e) => RunError::FailedToLoadProgram(path, e),
+        }
+    }
+}
+
+fn task_from_file(file: &Path, entrypoint: &str) -> Result<TaskSpec, TaskError> {
+    let task = match detect_task_file_type(file)? {
+        TaskFileType::CairoPieZip => Task::Pie(CairoPie::from_file(file)?),
+        TaskFileType::CairoPieDirectory => {
+            let zip_file = zip_cairo_pie_directory(file)?;
+            Task::Pie(CairoPie::from_file(zip_file.path())?)
+        }
+        TaskFileType::Cairo0Program => Task::Program(load_program(file, entrypoint)?),
+        file_type => return Err(TaskError::Unsupported(file_type)),
     };
 
     Ok(TaskSpec { task })
 }
 
+/// Runs a Sierra task beforehand and returns it as a Cairo PIE, as the bootloader only
+/// runs Cairo 0 programs.
+fn load_sierra_task(
+    task: &BootloaderTask,
+    path: &Path,
+    layout: Layout,
+) -> Result<TaskSpec, RunError> {
+    if task.program_input.is_some() {
+        return Err(RunError::ProgramInputForSierra(path.to_path_buf()));
+    }
+    let sierra_error = |e| RunError::Sierra(path.to_path_buf(), e);
+    let program = load_sierra_program(path).map_err(sierra_error)?;
+    let cairo_pie =
+        sierra_to_cairo_pie(&program, task.args.as_ref(), layout).map_err(sierra_error)?;
+
+    Ok(TaskSpec {
+        task: Task::Pie(cairo_pie),
+    })
+}
+
+fn load_task(
+    task: &BootloaderTask,
+    layout: Layout,
+    allow_missing_builtins: bool,
+    max_steps: Option<usize>,
+) -> Result<TaskSpec, RunError> {
+    let name = task.name();
+    let entrypoint = task.entrypoint.as_deref().unwrap_or(DEFAULT_ENTRYPOINT);
+
+    let (program, program_json) = match &task.program {
+        TaskProgram::Path(path) => {
+            let file_type = detect_task_file_type(path)
+                .map_err(|e| RunError::UnknownTaskType(path.clone(), e))?;
+            if file_type == TaskFileType::Sierra {
+                if max_steps.is_some() {
+                    return Err(RunError::MaxStepsForSierra(path.clone()));
+                }
+                return load_sierra_task(task, path, layout);
+            }
+            if task.args.is_some() {
+                return Err(RunError::FunctionArgsForCairo0(path.clone()));
+            }
+            if task.program_input.is_none() {
+                return task_from_file(path, entrypoint).map_err(|e| e.into_run_error(path));
+            }
+            match file_type {
+                TaskFileType::Cairo0Program => (
+                    load_program(path, entrypoint),
+                    Cow::Owned(read_program_json(path)?),
+                ),
+                TaskFileType::CairoPieZip | TaskFileType::CairoPieDirectory => {
+                    return Err(RunError::ProgramInputForPie(path.clone()));
+                }
+                file_type => return Err(RunError::UnsupportedTaskType(path.clone(), file_type)),
+            }
+        }
+        TaskProgram::Inline { program, .. } => (
+            Program::from_bytes(program.to_string().as_bytes(), Some(entrypoint)),
+            Cow::Borrowed(program),
+        ),
+    };
+    let program = program.map_err(|e| RunError::FailedToLoadProgram(name.to_path_buf(), e))?;
+
+    let program_input = match &task.program_input {
+        None => {
+            return Ok(TaskSpec {
+                task: Task::Program(program),
+            })
+        }
+        Some(ProgramInput::Path(path)) => read_program_input(path)?,
+        Some(ProgramInput::Inline(value)) => value.clone(),
+    };
+    let program_input = load_program_input(name, &program_json, program_input)?;
+
+    // The bootloader does not forward inputs to program tasks. Instead, run the program
+    // with its input beforehand and let the bootloader run the resulting Cairo PIE.
+    let cairo_pie = run_to_cairo_pie(
+        &program,
+        Some(&program_input),
+        entrypoint,
+        layout,
+        allow_missing_builtins,
+        max_steps,
+    )?;
+
+    Ok(TaskSpec {
+        task: Task::Pie(cairo_pie),
+    })
+}
+
 pub fn run_with_bootloader(
     bootloader: Bootloader,
-    executables: &[PathBuf],
-    layout: Layout,
+    tasks: &[BootloaderTask],
+    layout: Option<Layout>,
+    verifier: &Verifier,
     allow_missing_builtins: bool,
+    max_steps: Option<usize>,
     fact_topologies_path: Option<PathBuf>,
 ) -> Result<ExecutionArtifacts, RunError> {
+    check_layout(layout)?;
     let bootloader_program = load_bootloader(bootloader)?;
-    let tasks: Result<Vec<TaskSpec>, RunError> = executables
+    // Tasks with a program input are pre-run outside of proof mode, where the layout
+    // only needs to provide the builtins of the task.
+    let task_layout = layout.unwrap_or(PROBE_LAYOUT);
+    let tasks: Result<Vec<TaskSpec>, RunError> = tasks
         .iter()
-        .map(|path| {
-            task_from_file(path).map_err(|e| match e {
-                TaskError::Pie(e) => RunError::FailedToLoadPie(path.to_path_buf(), e),
-                TaskError::Program(e) => RunError::FailedToLoadProgram(path.to_path_buf(), e),
-            })
-        })
+        .map(|task| load_task(task, task_layout, allow_missing_builtins, max_steps))
         .collect();
     let tasks = tasks?;
+
+    // The bootloader run cannot be interrupted, the tasks are measured beforehand to
+    // enforce the step limit and to select the layout.
+    let (layout, allow_missing_builtins) = match layout {
+        Some(layout) => {
+            if max_steps.is_some() {
+                measure_tasks(&tasks, max_steps)?;
+            }
+            (layout, allow_missing_builtins)
+        }
+        None => {
+            // The bootloader declares all the builtins, but the layout only needs to
+            // provide the ones that it or the tasks use.
+            let usage = measure_tasks(&tasks, max_steps)?;
+            (select_bootloader_layout(&usage, verifier), true)
+        }
+    };
     run_bootloader_in_proof_mode(
         &bootloader_program,
         tasks,
@@ -141,6 +773,159 @@ pub fn run_with_bootloader(
     .map_err(|e| e.into())
 }
 
+/// Prints the resources used by the execution against the capacity of its layout.
+pub fn print_execution_report(
+    execution_artifacts: &ExecutionArtifacts,
+    format: OutputFormat,
+) -> Result<(), RunError> {
+    let report = ExecutionReport::new(execution_artifacts);
+    match format {
+        OutputFormat::Text => report.print(),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+/// Prints the output of the program and/or writes it to a file, as requested.
+fn export_program_output(
+    public_input: &PublicInput,
+    with_bootloader: bool,
+    args: &ProgramOutputArgs,
+) -> Result<(), RunError> {
+    if !args.print_output && args.output_values_file.is_none() {
+        return Ok(());
+    }
+
+    let output_values = OutputValues::new(public_input, with_bootloader, args.output_encoding)?;
+    if args.print_output {
+        output_values.print();
+    }
+    if let Some(path) = &args.output_values_file {
+        write_pretty_json_to_file(&output_values, path)
+            .map_err(|e| RunError::Write(path.clone(), e))?;
+    }
+
+    Ok(())
+}
+
+/// Loads execution artifacts generated ahead of time, skipping the Cairo VM entirely.
+pub fn load_execution_artifacts(
+    paths: &ExecutionArtifactsPaths,
+) -> Result<ExecutionArtifacts, RunError> {
+    let public_input: PublicInput = read_json_from_file(&paths.public_input)
+        .map_err(|e| RunError::Deserialize(paths.public_input.clone(), e))?;
+    let private_input: AirPrivateInputSerializable = read_json_from_file(&paths.private_input)
+        .map_err(|e| RunError::Deserialize(paths.private_input.clone(), e))?;
+    let memory = std::fs::read(&paths.memory).map_err(|e| RunError::Io(paths.memory.clone(), e))?;
+    let trace = std::fs::read(&paths.trace).map_err(|e| RunError::Io(paths.trace.clone(), e))?;
+
+    Ok(ExecutionArtifacts {
+        public_input,
+        private_input: AirPrivateInput::from(private_input),
+        memory,
+        trace,
+    })
+}
+
+/// Runs the executable in proof mode and returns the artifacts required by the prover.
+pub fn execute(
+    executable: Executable,
+    layout: Option<Layout>,
+    verifier: &Verifier,
+    allow_missing_builtins: bool,
+    limits: ExecutionLimits,
+    outputs: ExecutionOutputs,
+    fact_topologies_path: Option<PathBuf>,
+) -> Result<ExecutionArtifacts, RunError> {
+    let execution_artifacts = match executable {
+        Executable::BareMetal {
+            program,
+            program_input,
+            args,
+            entrypoint,
+        } => run_program(
+            program,
+            program_input.as_deref(),
+            args.as_ref(),
+            entrypoint.as_deref(),
+            layout,
+            verifier,
+            allow_missing_builtins,
+            limits.max_steps,
+            outputs,
+        )?,
+        Executable::WithBootloader {
+            bootloader,
+            tasks,
+            task_manifest,
+            bootloader_input,
+            entrypoint,
+        } => {
+            let mut tasks: Vec<BootloaderTask> =
+                tasks.into_iter().map(BootloaderTask::from_path).collect();
+            let mut fact_topologies_path = fact_topologies_path;
+            if let Some(task_manifest) = task_manifest {
+                let manifest_tasks = read_task_manifest(&task_manifest)
+                    .map_err(|e| RunError::InvalidTaskManifest(task_manifest, e))?;
+                tasks.extend(manifest_tasks);
+            }
+            if let Some(bootloader_input) = bootloader_input {
+                let input = read_bootloader_input(&bootloader_input)
+                    .map_err(|e| RunError::InvalidTaskManifest(bootloader_input, e))?;
+                if input.single_page {
+                    warn!("single_page is not supported and will be ignored.");
+                }
+                tasks.extend(input.tasks);
+                fact_topologies_path = fact_topologies_path.or(input.fact_topologies_path);
+            }
+            if let Some(entrypoint) = entrypoint {
+                tasks
+                    .iter_mut()
+                    .filter(|task| task.entrypoint.is_none())
+                    .for_each(|task| task.entrypoint = Some(entrypoint.clone()));
+            }
+            run_with_bootloader(
+                bootloader,
+                &tasks,
+                layout,
+                verifier,
+                allow_missing_builtins,
+                limits.max_steps,
+                fact_topologies_path,
+            )?
+        }
+        Executable::PreComputed(paths) => return load_execution_artifacts(&paths),
+    };
+    check_memory_cells(&execution_artifacts, limits.max_memory_cells)?;
+
+    Ok(execution_artifacts)
+}
+
+/// Writes the execution artifacts, prover config and parameters to `artifacts_dir`
+/// with the file names expected by the Stone binaries.
+fn keep_prover_inputs(
+    execution_artifacts: &ExecutionArtifacts,
+    prover_config: &ProverConfig,
+    prover_parameters: &ProverParameters,
+    artifacts_dir: &Path,
+) -> Result<(), RunError> {
+    write_execution_artifacts(execution_artifacts, artifacts_dir)?;
+
+    let prover_config_file = artifacts_dir.join(PROVER_CONFIG_FILE);
+    write_pretty_json_to_file(prover_config, &prover_config_file)
+        .map_err(|e| RunError::Write(prover_config_file, e))?;
+    let parameter_file = artifacts_dir.join(PARAMETER_FILE);
+    write_pretty_json_to_file(prover_parameters, &parameter_file)
+        .map_err(|e| RunError::Write(parameter_file, e))?;
+
+    info!(
+        "prover inputs written to {}.",
+        artifacts_dir.to_string_lossy()
+    );
+    Ok(())
+}
+
 pub fn prove(command: ProveCommand) -> Result<(), RunError> {
     debug!("preparing config files...");
 
@@ -160,25 +945,48 @@ pub fn prove(command: ProveCommand) -> Result<(), RunError> {
         .map(|path| read_json_from_file(path).map_err(|e| RunError::Deserialize(path.clone(), e)))
         .transpose()?;
 
-    info!("execution in progress...");
-    let execution_artifacts = match command.executable {
-        Executable::BareMetal(program_path) => {
-            run_program(program_path, command.layout, command.allow_missing_builtins)?
-        }
-        Executable::WithBootloader(bootloader, executables) => run_with_bootloader(
-            bootloader,
-            &executables,
-            command.layout,
-            command.allow_missing_builtins,
-            command.config.fact_topologies_file,
-        )?,
-    };
+    match command.executable {
+        Executable::PreComputed(_) => info!("loading execution artifacts..."),
+        _ => info!("execution in progress..."),
+    }
+    let with_bootloader = matches!(command.executable, Executable::WithBootloader { .. });
+    let execution_artifacts = execute(
+        command.executable,
+        command.layout,
+        &command.verifier,
+        command.allow_missing_builtins,
+        command.limits,
+        ExecutionOutputs {
+            cairo_pie: command.cairo_pie_output.as_deref(),
+            profile: command.profile.as_deref(),
+        },
+        command.config.fact_topologies_file,
+    )?;
+    if let Some(format) = command.report {
+        print_execution_report(&execution_artifacts, format)?;
+    }
+    write_cairo_run_files(&execution_artifacts, &command.cairo_run_files)?;
+    export_program_output(
+        &execution_artifacts.public_input,
+        with_bootloader,
+        &command.program_output,
+    )?;
 
     let prover_parameters = user_prover_parameters.unwrap_or(generate_prover_parameters(
         execution_artifacts.public_input.n_steps,
         command.verifier,
     ));
 
+    // Written before proving, so that failed proofs can be reproduced
+    if let Some(artifacts_dir) = &command.keep_artifacts {
+        keep_prover_inputs(
+            &execution_artifacts,
+            &prover_config,
+            &prover_parameters,
+            artifacts_dir,
+        )?;
+    }
+
     info!("proving in progress...");
     let proof = run_prover(
         &execution_artifacts.public_input,
@@ -192,21 +1000,7 @@ pub fn prove(command: ProveCommand) -> Result<(), RunError> {
 
     let output_file = config_args.output_file();
     write_json_to_file(proof, output_file.as_ref())
-        .map_err(|e| RunError::Io(output_file.into_owned(), e))?;
+        .map_err(|e| RunError::Write(output_file.into_owned(), e))?;
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rstest::rstest;
-    use std::path::PathBuf;
-
-    #[rstest]
-    #[case("cairo_pie.zip", true)]
-    #[case("program.json", false)]
-    fn test_is_zip_file(#[case] file: PathBuf, #[case] expected: bool) {
-        assert_eq!(is_zip_file(file.as_path()), expected);
-    }
-}
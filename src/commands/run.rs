@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use log::info;
+use stone_prover_sdk::cairo_vm::ExecutionArtifacts;
+
+use crate::cli::{CairoRunFiles, RunCommand};
+use crate::commands::prove::{execute, print_execution_report, ExecutionOutputs, RunError};
+use crate::toolkit::json::write_json_to_file;
+
+const PUBLIC_INPUT_FILE: &str = "public_input.json";
+const PRIVATE_INPUT_FILE: &str = "private_input.json";
+const MEMORY_FILE: &str = "memory.bin";
+const TRACE_FILE: &str = "trace.bin";
+
+/// Resolves `path` against the current directory, so that the private input stays valid
+/// wherever the prover is run from.
+fn absolute_path(path: &Path) -> Result<String, RunError> {
+    std::env::current_dir()
+        .map(|dir| dir.join(path).to_string_lossy().to_string())
+        .map_err(|e| RunError::Write(path.to_path_buf(), e))
+}
+
+/// Writes the public input, private input, memory and trace files in `output_dir`.
+///
+/// The private input references the memory and trace files with absolute paths, so the
+/// directory can be fed directly to the Stone prover.
+pub fn write_execution_artifacts(
+    execution_artifacts: &ExecutionArtifacts,
+    output_dir: &Path,
+) -> Result<(), RunError> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| RunError::Write(output_dir.to_path_buf(), e))?;
+
+    let public_input_file = output_dir.join(PUBLIC_INPUT_FILE);
+    let private_input_file = output_dir.join(PRIVATE_INPUT_FILE);
+    let memory_file = output_dir.join(MEMORY_FILE);
+    let trace_file = output_dir.join(TRACE_FILE);
+
+    write_json_to_file(&execution_artifacts.public_input, &public_input_file)
+        .map_err(|e| RunError::Write(public_input_file, e))?;
+
+    let private_input = execution_artifacts
+        .private_input
+        .to_serializable(absolute_path(&trace_file)?, absolute_path(&memory_file)?);
+    write_json_to_file(private_input, &private_input_file)
+        .map_err(|e| RunError::Write(private_input_file, e))?;
+
+    std::fs::write(&memory_file, &execution_artifacts.memory)
+        .map_err(|e| RunError::Write(memory_file, e))?;
+    std::fs::write(&trace_file, &execution_artifacts.trace)
+        .map_err(|e| RunError::Write(trace_file, e))?;
+
+    Ok(())
+}
+
+/// Writes the execution artifacts requested in `files`, in the same format as `cairo-run`.
+pub fn write_cairo_run_files(
+    execution_artifacts: &ExecutionArtifacts,
+    files: &CairoRunFiles,
+) -> Result<(), RunError> {
+    if let Some(trace_file) = &files.trace_file {
+        std::fs::write(trace_file, &execution_artifacts.trace)
+            .map_err(|e| RunError::Write(trace_file.clone(), e))?;
+    }
+    if let Some(memory_file) = &files.memory_file {
+        std::fs::write(memory_file, &execution_artifacts.memory)
+            .map_err(|e| RunError::Write(memory_file.clone(), e))?;
+    }
+    if let Some(public_input_file) = &files.air_public_input {
+        write_json_to_file(&execution_artifacts.public_input, public_input_file)
+            .map_err(|e| RunError::Write(public_input_file.clone(), e))?;
+    }
+    if let Some(private_input_file) = &files.air_private_input {
+        // Enforced by clap
+        let trace_file = absolute_path(files.trace_file.as_ref().unwrap())?;
+        let memory_file = absolute_path(files.memory_file.as_ref().unwrap())?;
+        let private_input = execution_artifacts
+            .private_input
+            .to_serializable(trace_file, memory_file);
+        write_json_to_file(private_input, private_input_file)
+            .map_err(|e| RunError::Write(private_input_file.clone(), e))?;
+    }
+
+    Ok(())
+}
+
+pub fn run(command: RunCommand) -> Result<(), RunError> {
+    info!("execution in progress...");
+    let execution_artifacts = execute(
+        command.executable,
+        command.layout,
+        &command.verifier,
+        command.allow_missing_builtins,
+        command.limits,
+        ExecutionOutputs {
+            cairo_pie: command.cairo_pie_output.as_deref(),
+            profile: command.profile.as_deref(),
+        },
+        command.fact_topologies_file,
+    )?;
+    info!(
+        "execution completed in {} steps.",
+        execution_artifacts.public_input.n_steps
+    );
+    if let Some(format) = command.report {
+        print_execution_report(&execution_artifacts, format)?;
+    }
+
+    write_cairo_run_files(&execution_artifacts, &command.cairo_run_files)?;
+    write_execution_artifacts(&execution_artifacts, &command.output_dir)?;
+    info!(
+        "execution artifacts written to {}.",
+        command.output_dir.to_string_lossy()
+    );
+
+    Ok(())
+}
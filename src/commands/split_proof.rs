@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use log::{info, warn};
+use serde::Serialize;
+use stark_evm_adapter::annotated_proof::AnnotatedProof;
+use stark_evm_adapter::annotation_parser::{split_fri_merkle_statements, SplitProofs};
+use stone_prover_sdk::error::VerifierError;
+use stone_prover_sdk::models::Proof;
+
+use crate::cli::SplitProofArgs;
+use crate::toolkit::json::{read_json_from_file, write_json_to_file, ReadJsonError};
+
+const STARK_PRIME: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
+
+const ANNOTATIONS_FILE: &str = "annotations.txt";
+const EXTRA_ANNOTATIONS_FILE: &str = "extra_annotations.txt";
+const ANNOTATED_PROOF_FILE: &str = "annotated_proof.json";
+const MAIN_PROOF_FILE: &str = "main_proof.json";
+const TRACE_MERKLE_STATEMENTS_FILE: &str = "trace_merkle_statements.json";
+const FRI_MERKLE_STATEMENTS_FILE: &str = "fri_merkle_statements.json";
+const MEMORY_PAGES_FILE: &str = "memory_pages.json";
+
+#[derive(thiserror::Error, Debug)]
+pub enum SplitProofError {
+    #[error("Failed to read file {0}: {1}")]
+    Read(PathBuf, std::io::Error),
+
+    #[error("Failed to write file {0}: {1}")]
+    Write(PathBuf, std::io::Error),
+
+    #[error("Failed to create directory {0}: {1}")]
+    CreateDir(PathBuf, std::io::Error),
+
+    #[error("Failed to deserialize {0}: {1}")]
+    Deserialize(PathBuf, ReadJsonError),
+
+    #[error(transparent)]
+    Verifier(#[from] VerifierError),
+
+    #[error("Failed to parse the proof annotations: {0}")]
+    InvalidAnnotations(String),
+
+    #[error("Memory page {page} is not continuous at address {address}")]
+    NonContinuousPage { page: u64, address: u64 },
+
+    #[error(transparent)]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Arguments of `MemoryPageFactRegistry.registerContinuousMemoryPage` for one page.
+#[derive(Serialize, Debug, PartialEq)]
+struct ContinuousMemoryPage {
+    page: u64,
+    start_address: u64,
+    values: Vec<String>,
+    z: serde_json::Value,
+    alpha: serde_json::Value,
+    prime: &'static str,
+}
+
+/// Runs the verifier to generate the annotations of the proof.
+fn generate_annotations(
+    proof_file: &Path,
+    annotations_file: &Path,
+    extra_annotations_file: &Path,
+) -> Result<(), VerifierError> {
+    let output = Command::new("cpu_air_verifier")
+        .arg("--in_file")
+        .arg(proof_file)
+        .arg("--annotation_file")
+        .arg(annotations_file)
+        .arg("--extra_output_file")
+        .arg(extra_annotations_file)
+        .output()
+        .map_err(VerifierError::IoError)?;
+
+    if !output.status.success() {
+        return Err(VerifierError::CommandError(output));
+    }
+
+    Ok(())
+}
+
+fn parse_annotations(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn read_annotations(path: &Path) -> Result<Vec<String>, SplitProofError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| SplitProofError::Read(path.to_path_buf(), e))?;
+    Ok(parse_annotations(&content))
+}
+
+/// Splits the FRI and trace Merkle statements out of a proof and its annotations.
+fn split_annotated_proof(
+    annotated_proof: serde_json::Value,
+) -> Result<SplitProofs, SplitProofError> {
+    let annotated_proof: AnnotatedProof = serde_json::from_value(annotated_proof)
+        .map_err(|e| SplitProofError::InvalidAnnotations(e.to_string()))?;
+    split_fri_merkle_statements(annotated_proof)
+        .map_err(|e| SplitProofError::InvalidAnnotations(format!("{e:?}")))
+}
+
+/// Groups the public memory cells, given as `(page, address, value)`, by page. Page 0 is
+/// registered as a regular memory page by the verifier, the other pages must be registered
+/// as continuous pages beforehand: their addresses must follow each other.
+fn continuous_memory_pages<'a>(
+    public_memory: impl IntoIterator<Item = (u64, u64, &'a str)>,
+    z: &serde_json::Value,
+    alpha: &serde_json::Value,
+) -> Result<Vec<ContinuousMemoryPage>, SplitProofError> {
+    let mut pages: BTreeMap<u64, Vec<(u64, &str)>> = BTreeMap::new();
+    for (page, address, value) in public_memory {
+        if page != 0 {
+            pages.entry(page).or_default().push((address, value));
+        }
+    }
+
+    pages
+        .into_iter()
+        .map(|(page, mut cells)| {
+            cells.sort_by_key(|(address, _)| *address);
+            let start_address = cells.first().map(|(address, _)| *address).unwrap_or(0);
+            // Also rejects duplicate addresses
+            let gap = cells
+                .iter()
+                .zip(start_address..)
+                .find(|((address, _), expected_address)| address != expected_address);
+            if let Some(((address, _), _)) = gap {
+                return Err(SplitProofError::NonContinuousPage {
+                    page,
+                    address: *address,
+                });
+            }
+
+            Ok(ContinuousMemoryPage {
+                page,
+                start_address,
+                values: cells.iter().map(|(_, value)| value.to_string()).collect(),
+                z: z.clone(),
+                alpha: alpha.clone(),
+                prime: STARK_PRIME,
+            })
+        })
+        .collect()
+}
+
+fn write_output_file<T: Serialize>(
+    obj: T,
+    output_dir: &Path,
+    name: &str,
+) -> Result<(), SplitProofError> {
+    let path = output_dir.join(name);
+    write_json_to_file(obj, &path).map_err(|e| SplitProofError::Write(path, e))
+}
+
+pub fn split_proof(args: SplitProofArgs) -> Result<(), SplitProofError> {
+    let output_dir = args.output_dir();
+    let output_dir = output_dir.as_path();
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| SplitProofError::CreateDir(output_dir.to_path_buf(), e))?;
+
+    let proof: Proof = read_json_from_file(&args.proof_file)
+        .map_err(|e| SplitProofError::Deserialize(args.proof_file.clone(), e))?;
+    if proof.proof_parameters.stark.fri.last_layer_degree_bound != 32 {
+        warn!("the proof parameters do not look compatible with the L1 verifier, did you prove with `--verifier l1`?");
+    }
+
+    info!("generating proof annotations...");
+    let annotations_file = output_dir.join(ANNOTATIONS_FILE);
+    let extra_annotations_file = output_dir.join(EXTRA_ANNOTATIONS_FILE);
+    generate_annotations(&args.proof_file, &annotations_file, &extra_annotations_file)?;
+
+    let mut annotated_proof = serde_json::to_value(&proof)?;
+    annotated_proof["annotations"] = read_annotations(&annotations_file)?.into();
+    annotated_proof["extra_annotations"] = read_annotations(&extra_annotations_file)?.into();
+    write_output_file(&annotated_proof, output_dir, ANNOTATED_PROOF_FILE)?;
+
+    info!("splitting proof...");
+    let split_proofs = split_annotated_proof(annotated_proof)?;
+
+    write_output_file(&split_proofs.main_proof, output_dir, MAIN_PROOF_FILE)?;
+    write_output_file(
+        &split_proofs.merkle_statements,
+        output_dir,
+        TRACE_MERKLE_STATEMENTS_FILE,
+    )?;
+    write_output_file(
+        &split_proofs.fri_merkle_statements,
+        output_dir,
+        FRI_MERKLE_STATEMENTS_FILE,
+    )?;
+    let public_memory = proof.public_input.public_memory.iter().map(|entry| {
+        (
+            entry.page as u64,
+            entry.address as u64,
+            entry.value.as_str(),
+        )
+    });
+    let z = serde_json::to_value(&split_proofs.main_proof.interaction_z)?;
+    let alpha = serde_json::to_value(&split_proofs.main_proof.interaction_alpha)?;
+    write_output_file(
+        continuous_memory_pages(public_memory, &z, &alpha)?,
+        output_dir,
+        MEMORY_PAGES_FILE,
+    )?;
+    info!("split proof written to {}.", output_dir.to_string_lossy());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn test_continuous_memory_pages() {
+        let public_memory = [
+            (0, 1, "0x1"),
+            (2, 12, "0xc"),
+            (1, 21, "0x15"),
+            (2, 10, "0xa"),
+            (1, 20, "0x14"),
+            (2, 11, "0xb"),
+        ];
+        let z = serde_json::json!("0x5");
+        let alpha = serde_json::json!("0x6");
+
+        let pages = continuous_memory_pages(public_memory, &z, &alpha).unwrap();
+        let expected_page = |page, start_address, values: &[&str]| ContinuousMemoryPage {
+            page,
+            start_address,
+            values: values.iter().map(|value| value.to_string()).collect(),
+            z: z.clone(),
+            alpha: alpha.clone(),
+            prime: STARK_PRIME,
+        };
+        assert_eq!(
+            pages,
+            vec![
+                expected_page(1, 20, &["0x14", "0x15"]),
+                expected_page(2, 10, &["0xa", "0xb", "0xc"]),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::gap(&[(1, 20, "0x14"), (1, 22, "0x16")], 22)]
+    #[case::duplicate(&[(1, 20, "0x14"), (1, 21, "0x15"), (1, 21, "0x15")], 21)]
+    fn test_non_continuous_memory_page(
+        #[case] public_memory: &[(u64, u64, &str)],
+        #[case] expected_address: u64,
+    ) {
+        let result = continuous_memory_pages(
+            public_memory.iter().copied(),
+            &serde_json::Value::Null,
+            &serde_json::Value::Null,
+        );
+        assert!(matches!(
+            result,
+            Err(SplitProofError::NonContinuousPage { page: 1, address }) if address == expected_address
+        ));
+    }
+
+    #[test]
+    fn test_parse_annotations() {
+        let content = "P->V[0:32]: /cpu air/STARK/Original/Commit on Trace: Commitment: Hash(0x1)\n\n  V->P: /cpu air/STARK/Interaction: Interaction element #0: Field Element(0x2)  \n";
+        assert_eq!(
+            parse_annotations(content),
+            vec![
+                "P->V[0:32]: /cpu air/STARK/Original/Commit on Trace: Commitment: Hash(0x1)",
+                "V->P: /cpu air/STARK/Interaction: Interaction element #0: Field Element(0x2)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_missing_annotations() {
+        let annotations_dir = tempfile::tempdir().unwrap();
+        let annotations_file = annotations_dir.path().join(ANNOTATIONS_FILE);
+        assert!(matches!(
+            read_annotations(&annotations_file),
+            Err(SplitProofError::Read(path, _)) if path == annotations_file
+        ));
+    }
+
+    #[test]
+    fn test_split_invalid_annotated_proof() {
+        let annotated_proof = serde_json::json!({
+            "annotations": ["not an annotation"],
+            "extra_annotations": [],
+        });
+        assert!(matches!(
+            split_annotated_proof(annotated_proof),
+            Err(SplitProofError::InvalidAnnotations(_))
+        ));
+    }
+}
@@ -1,13 +1,115 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use cairo_vm::Felt252;
 use log::info;
 use stone_prover_sdk::error::VerifierError;
+use stone_prover_sdk::models::Proof;
 use stone_prover_sdk::verifier::run_verifier;
 
 use crate::cli::VerifyArgs;
+use crate::toolkit::felt::{load_felt_list, LoadFeltListError};
+use crate::toolkit::json::{read_json_from_file, ReadJsonError};
+use crate::toolkit::output::{read_program_output, OutputError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum VerifyError {
+    #[error(transparent)]
+    Verifier(#[from] VerifierError),
+
+    #[error("Failed to deserialize {0}: {1}")]
+    Deserialize(PathBuf, ReadJsonError),
+
+    #[error(transparent)]
+    InvalidExpectedOutput(#[from] LoadFeltListError),
+
+    #[error(transparent)]
+    Output(#[from] OutputError),
+
+    #[error("Program output does not match the expected output:\n{0}")]
+    OutputMismatch(String),
+}
+
+/// Returns a human-readable diff between the two outputs, or `None` if they are equal.
+fn diff_outputs(output: &[Felt252], expected_output: &[Felt252]) -> Option<String> {
+    let mut diff = String::new();
+
+    if output.len() != expected_output.len() {
+        let _ = writeln!(
+            diff,
+            "  length: expected {}, got {}",
+            expected_output.len(),
+            output.len()
+        );
+    }
+
+    for index in 0..output.len().max(expected_output.len()) {
+        match (output.get(index), expected_output.get(index)) {
+            (Some(value), Some(expected)) if value == expected => {}
+            (value, expected) => {
+                let format = |felt: Option<&Felt252>| match felt {
+                    Some(felt) => felt.to_string(),
+                    None => "<missing>".to_string(),
+                };
+                let _ = writeln!(
+                    diff,
+                    "  output[{index}]: expected {}, got {}",
+                    format(expected),
+                    format(value)
+                );
+            }
+        }
+    }
 
-pub fn verify(args: VerifyArgs) -> Result<(), VerifierError> {
+    match diff.is_empty() {
+        true => None,
+        false => Some(diff),
+    }
+}
+
+fn check_output(proof_file: &Path, expected_output: &str) -> Result<(), VerifyError> {
+    let expected_output = load_felt_list(expected_output)?;
+    let proof: Proof = read_json_from_file(proof_file)
+        .map_err(|e| VerifyError::Deserialize(proof_file.to_path_buf(), e))?;
+    let output = read_program_output(&proof.public_input)?;
+
+    match diff_outputs(&output, &expected_output) {
+        Some(diff) => Err(VerifyError::OutputMismatch(diff)),
+        None => Ok(()),
+    }
+}
+
+pub fn verify(args: VerifyArgs) -> Result<(), VerifyError> {
     info!("verification in progress...");
     run_verifier(args.proof_file.as_path())?;
     info!("verification completed!");
 
+    if let Some(expected_output) = args.expected_output.as_deref() {
+        check_output(&args.proof_file, expected_output)?;
+        info!("program output matches the expected output.");
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_outputs() {
+        let output = vec![Felt252::from(1), Felt252::from(2)];
+
+        assert_eq!(diff_outputs(&output, &output), None);
+        assert_eq!(
+            diff_outputs(&output, &[Felt252::from(1), Felt252::from(3)]),
+            Some("  output[1]: expected 3, got 2\n".to_string())
+        );
+        assert_eq!(
+            diff_outputs(&output, &[Felt252::from(1)]),
+            Some(
+                "  length: expected 1, got 2\n  output[1]: expected <missing>, got 2\n".to_string()
+            )
+        );
+    }
+}
@@ -1,5 +1,11 @@
-use crate::cli::Cli;
+use crate::cli::{Cli, ConfigCommand, ParamsCommand, ProofCommand};
+use crate::commands::batch::BatchError;
+use crate::commands::fact::FactCommandError;
+use crate::commands::inspect::InspectError;
+use crate::commands::params::ParamsError;
 use crate::commands::prove::RunError;
+use crate::commands::split_proof::SplitProofError;
+use crate::commands::verify::VerifyError;
 use cairo_vm::vm::errors::cairo_run_errors::CairoRunError;
 use clap::Parser;
 use env_logger::fmt::Formatter;
@@ -18,7 +24,17 @@ enum CliError {
     #[error(transparent)]
     Prove(#[from] RunError),
     #[error(transparent)]
-    Verify(#[from] VerifierError),
+    Verify(#[from] VerifyError),
+    #[error(transparent)]
+    Inspect(#[from] InspectError),
+    #[error(transparent)]
+    Fact(#[from] FactCommandError),
+    #[error(transparent)]
+    SplitProof(#[from] SplitProofError),
+    #[error(transparent)]
+    Params(#[from] ParamsError),
+    #[error(transparent)]
+    Batch(#[from] BatchError),
 }
 
 fn format_log(buf: &mut Formatter, record: &Record) -> io::Result<()> {
@@ -38,56 +54,300 @@ fn setup_logging() {
         .init();
 }
 
+fn run_error_message(run_error: RunError) -> String {
+    match run_error {
+        RunError::Io(path_buf, io_error) => {
+            format!("could not read {}: {io_error}.", path_buf.to_string_lossy())
+        }
+        RunError::Write(path_buf, io_error) => {
+            format!(
+                "could not write {}: {io_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::Deserialize(path_buf, json_error) => {
+            format!(
+                "could not read JSON file {}: {json_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::FailedToLoadBootloader(program_error) => {
+            format!("failed to load bootloader program: {program_error}. This is an internal error and should not happen.")
+        }
+        RunError::FailedToLoadProgram(path_buf, program_error) => {
+            format!(
+                "failed to load program {}: {program_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::FailedToLoadPie(path_buf, pie_error) => {
+            format!(
+                "failed to load Cairo PIE {}: {pie_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::UnknownTaskType(path_buf, file_type_error) => {
+            format!(
+                "could not load task {}: {file_type_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::UnsupportedTaskType(path_buf, file_type) => {
+            format!(
+                "{} is a {file_type}, which is not supported as a task.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::InvalidTaskManifest(path_buf, manifest_error) => {
+            format!(
+                "could not read task file {}: {manifest_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::ProgramInput(path_buf, program_input_error) => {
+            format!(
+                "cannot run {} with its program input: {program_input_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::ProgramInputForPie(path_buf) => {
+            format!(
+                "cannot provide a program input for {}, Cairo PIEs have already been executed.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::ProgramInputForSierra(path_buf) => {
+            format!(
+                "cannot provide a program input for {}, pass the arguments of the Sierra program with --args instead.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::FunctionArgsForCairo0(path_buf) => {
+            format!(
+                "cannot pass function arguments to {}, they are only supported for Sierra programs. Use a program input instead.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::EntrypointForSierra(path_buf) => {
+            format!(
+                "cannot set the entrypoint of {}, only the `main` function of Sierra programs can be run.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::MissingBuiltinsForSierra(path_buf) => {
+            format!(
+                "cannot run {} with --allow-missing-builtins, the layout must provide all the builtins of Sierra programs.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::MaxStepsForSierra(path_buf) => {
+            format!(
+                "cannot run {} with --max-steps, Cairo 1 programs cannot be interrupted: their gas bounds the execution instead.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::InvalidEntrypoint(path_buf, entrypoint, e) => {
+            format!(
+                "cannot run {} from `{entrypoint}`: {e}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::CasmProgram(path_buf) => {
+            format!(
+                "cannot run {}: CASM files do not describe the arguments of the program. Run its Sierra program instead.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::Sierra(path_buf, sierra_error) => {
+            format!(
+                "failed to run Sierra program {}: {sierra_error}.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::ProfileNotSupported(path_buf) => {
+            format!(
+                "cannot profile {}, profiles require the debug info of Cairo 0 programs.",
+                path_buf.to_string_lossy()
+            )
+        }
+        RunError::Profile(profile_error) => {
+            format!("could not profile the execution: {profile_error}.")
+        }
+        RunError::LimitExceeded { resource, limit } => {
+            format!("execution aborted, the program exceeded the limit of {limit} {resource}.")
+        }
+        RunError::DynamicLayoutNotSupported => {
+            "the dynamic layout is not supported: Stone needs the trace columns of the dynamic AIR in the public input, which the CLI cannot generate, and the Cairo VM cannot be given builtin ratios. Use one of the fixed layouts instead.".to_string()
+        }
+        RunError::ProgramOutput(output_error) => {
+            format!("could not read the program output: {output_error}.")
+        }
+        RunError::FailedExecution(execution_error) => match execution_error {
+            ExecutionError::RunFailed(cairo_run_error) => match cairo_run_error {
+                CairoRunError::Program(program_error) => {
+                    format!("failed to load program: {program_error}")
+                }
+                other => format!("failed to run Cairo program: {other}"),
+            },
+            other => format!("failed to extract VM output(s): {other}"),
+        },
+        RunError::Serialize(serde_error) => {
+            format!("failed to serialize the execution report: {serde_error}.")
+        }
+        RunError::Prover(prover_error) => {
+            format!("failed to run prover: {prover_error}")
+        }
+    }
+}
+
 fn display_error(error: CliError) {
     let error_message = match error {
-        CliError::Prove(run_error) => match run_error {
-            RunError::Io(path_buf, io_error) => {
-                format!("could not read {}: {io_error}.", path_buf.to_string_lossy())
+        CliError::Prove(run_error) => run_error_message(run_error),
+        CliError::Verify(verify_error) => match verify_error {
+            VerifyError::Verifier(e) => match e {
+                VerifierError::IoError(_) => {
+                    "could not find verifier program. Is cpu_air_verifier installed?".to_string()
+                }
+                VerifierError::CommandError(command_output) => {
+                    format!(
+                        "failed to run verifier: {}",
+                        String::from_utf8_lossy(&command_output.stderr)
+                    )
+                }
+            },
+            VerifyError::Deserialize(path_buf, json_error) => {
+                format!(
+                    "could not read proof file {}: {json_error}.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            VerifyError::InvalidExpectedOutput(felt_error) => {
+                format!("could not parse expected output: {felt_error}.")
             }
-            RunError::Deserialize(path_buf, json_error) => {
+            VerifyError::Output(output_error) => {
+                format!("could not read program output from proof: {output_error}.")
+            }
+            VerifyError::OutputMismatch(diff) => {
+                format!("program output does not match the expected output:\n{diff}")
+            }
+        },
+        CliError::Inspect(e) => match e {
+            InspectError::Deserialize(path_buf, json_error) => {
+                format!(
+                    "could not read proof file {}: {json_error}.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            InspectError::Serialize(json_error) => {
+                format!("failed to serialize proof summary: {json_error}.")
+            }
+        },
+        CliError::Fact(e) => match e {
+            FactCommandError::Deserialize(path_buf, json_error) => {
                 format!(
                     "could not read JSON file {}: {json_error}.",
                     path_buf.to_string_lossy()
                 )
             }
-            RunError::FailedToLoadBootloader(program_error) => {
-                format!("failed to load bootloader program: {program_error}. This is an internal error and should not happen.")
+            FactCommandError::InvalidOutput(felt_error) => {
+                format!("could not parse program output: {felt_error}.")
+            }
+            FactCommandError::Output(output_error) => {
+                format!("could not read program output from proof: {output_error}.")
+            }
+            FactCommandError::InvalidBootloaderOutput(message) => {
+                format!("invalid bootloader output: {message}. Was the proof generated with the bootloader?")
+            }
+            other => format!("failed to compute fact: {other}."),
+        },
+        CliError::SplitProof(e) => match e {
+            SplitProofError::Read(path_buf, io_error) => {
+                format!("could not read {}: {io_error}.", path_buf.to_string_lossy())
             }
-            RunError::FailedToLoadProgram(path_buf, program_error) => {
+            SplitProofError::Write(path_buf, io_error) => {
                 format!(
-                    "failed to load program {}: {program_error}.",
+                    "could not write {}: {io_error}.",
                     path_buf.to_string_lossy()
                 )
             }
-            RunError::FailedToLoadPie(path_buf, pie_error) => {
+            SplitProofError::CreateDir(path_buf, io_error) => {
                 format!(
-                    "failed to load Cairo PIE {}: {pie_error}.",
+                    "could not create directory {}: {io_error}.",
                     path_buf.to_string_lossy()
                 )
             }
-            RunError::FailedExecution(execution_error) => match execution_error {
-                ExecutionError::RunFailed(cairo_run_error) => match cairo_run_error {
-                    CairoRunError::Program(program_error) => {
-                        format!("failed to load program: {program_error}")
-                    }
-                    other => format!("failed to run Cairo program: {other}"),
-                },
-                other => format!("failed to extract VM output(s): {other}"),
-            },
-            RunError::Prover(prover_error) => {
-                format!("failed to run prover: {prover_error}")
+            SplitProofError::NonContinuousPage { page, address } => {
+                format!("memory page {page} of the public memory is not continuous at address {address}, it cannot be registered for the L1 verifier.")
             }
-        },
-        CliError::Verify(e) => match e {
-            VerifierError::IoError(_) => {
+            SplitProofError::Deserialize(path_buf, json_error) => {
+                format!(
+                    "could not read proof file {}: {json_error}.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            SplitProofError::Verifier(VerifierError::IoError(_)) => {
                 "could not find verifier program. Is cpu_air_verifier installed?".to_string()
             }
-            VerifierError::CommandError(command_output) => {
+            SplitProofError::Verifier(VerifierError::CommandError(command_output)) => {
                 format!(
-                    "failed to run verifier: {}",
+                    "failed to generate proof annotations: {}",
                     String::from_utf8_lossy(&command_output.stderr)
                 )
             }
+            other => format!("failed to split proof: {other}."),
+        },
+        CliError::Params(e) => match e {
+            ParamsError::Run(run_error) => run_error_message(run_error),
+            ParamsError::Io(path_buf, io_error) => {
+                format!(
+                    "could not write {}: {io_error}.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            ParamsError::FileExists(path_buf) => {
+                format!(
+                    "{} already exists, use --force to overwrite it.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            ParamsError::TooManySteps(n_steps) => {
+                format!("cannot prove {n_steps} steps, the trace length must be a power of two below 2^32.")
+            }
+            ParamsError::UndocumentedField(pointer) => {
+                format!("documented field {pointer} is missing from the generated file. This is an internal error and should not happen.")
+            }
+            ParamsError::Serialize(json_error) => {
+                format!("failed to serialize prover parameters: {json_error}.")
+            }
+        },
+        CliError::Batch(e) => match e {
+            BatchError::Io(path_buf, io_error) => {
+                format!(
+                    "could not access {}: {io_error}.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            BatchError::Deserialize(path_buf, toml_error) => {
+                format!(
+                    "could not read manifest {}: {toml_error}",
+                    path_buf.to_string_lossy()
+                )
+            }
+            BatchError::Serialize(json_error) => {
+                format!("failed to serialize batch report: {json_error}.")
+            }
+            BatchError::DuplicateJobName(name) => {
+                format!("several jobs are named {name}, job names must be unique.")
+            }
+            BatchError::DuplicateOutputFile(path_buf) => {
+                format!(
+                    "several jobs write their proof to {}, set a distinct output_file for each job.",
+                    path_buf.to_string_lossy()
+                )
+            }
+            BatchError::FailedJobs(n_failed, n_jobs) => {
+                format!("{n_failed} out of {n_jobs} jobs failed, see the report for details.")
+            }
         },
     };
     error!("{}", error_message);
@@ -95,8 +355,21 @@ fn display_error(error: CliError) {
 
 fn process_cli_command(command: Cli) -> Result<(), CliError> {
     match command {
+        Cli::Run(run_args) => commands::run(run_args.command())?,
         Cli::Prove(prove_args) => commands::prove(prove_args.command())?,
         Cli::Verify(verify_args) => commands::verify(verify_args)?,
+        Cli::Proof(ProofCommand::Inspect(inspect_args)) => commands::inspect(inspect_args)?,
+        Cli::Proof(ProofCommand::Split(split_proof_args)) => {
+            commands::split_proof(split_proof_args)?
+        }
+        Cli::Fact(fact_args) => commands::fact(fact_args.command())?,
+        Cli::Params(ParamsCommand::Generate(generate_params_args)) => {
+            commands::generate_params(generate_params_args)?
+        }
+        Cli::Config(ConfigCommand::Init(init_config_args)) => {
+            commands::init_config(init_config_args)?
+        }
+        Cli::Batch(batch_args) => commands::batch(batch_args)?,
     };
 
     Ok(())
@@ -108,6 +381,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let command = Cli::parse();
     if let Err(e) = process_cli_command(command) {
         display_error(e);
+        std::process::exit(1);
     }
 
     Ok(())
@@ -0,0 +1,65 @@
+use std::fs::File;
+use std::path::Path;
+
+use tempfile::NamedTempFile;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Files of a Cairo PIE. `version.json` is missing from PIEs generated by older versions
+/// of cairo-lang.
+const CAIRO_PIE_FILES: [&str; 5] = [
+    "metadata.json",
+    "memory.bin",
+    "additional_data.json",
+    "execution_resources.json",
+    "version.json",
+];
+
+/// Packs an unzipped Cairo PIE directory into a temporary zip file that can be loaded with
+/// `CairoPie::from_file`. The files are stored without compression to keep this cheap.
+pub fn zip_cairo_pie_directory(dir: &Path) -> std::io::Result<NamedTempFile> {
+    let mut zip_file = NamedTempFile::new()?;
+    let mut zip_writer = ZipWriter::new(zip_file.as_file_mut());
+    let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+    for file_name in CAIRO_PIE_FILES {
+        let path = dir.join(file_name);
+        if !path.exists() && file_name == "version.json" {
+            continue;
+        }
+        let mut file = File::open(&path)?;
+        zip_writer.start_file(file_name, options)?;
+        std::io::copy(&mut file, &mut zip_writer)?;
+    }
+    zip_writer.finish()?;
+    drop(zip_writer);
+
+    Ok(zip_file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zip_cairo_pie_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        for file_name in &CAIRO_PIE_FILES[..4] {
+            std::fs::write(dir.path().join(file_name), file_name.as_bytes()).unwrap();
+        }
+
+        let zip_file = zip_cairo_pie_directory(dir.path()).unwrap();
+        let mut zip_archive = zip::ZipArchive::new(zip_file.reopen().unwrap()).unwrap();
+        assert_eq!(zip_archive.len(), 4);
+        let content = std::io::read_to_string(zip_archive.by_name("memory.bin").unwrap()).unwrap();
+        assert_eq!(content, "memory.bin");
+    }
+
+    #[test]
+    fn test_zip_incomplete_cairo_pie_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("metadata.json"), "{}").unwrap();
+
+        assert!(zip_cairo_pie_directory(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use cairo_vm::Felt252;
+
+use crate::toolkit::felt::parse_felt;
+
+/// `call rel <offset>`, the instruction of `__start__` that calls `main`.
+const CALL_REL: &str = "0x1104800180018000";
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum EntrypointError {
+    #[error("the program has no `{0}` function")]
+    MissingFunction(String),
+
+    #[error("the program was not compiled in proof mode, `__start__` does not call `main`")]
+    NotProofMode,
+
+    #[error("`{0}` must take the same arguments and return the same values as `main`")]
+    SignatureMismatch(String),
+}
+
+/// Makes the proof mode entry code of a compiled Cairo 0 program call `entrypoint`
+/// instead of `main`.
+///
+/// In proof mode, the Cairo VM starts at `__start__`, which reserves the arguments of
+/// `main`, calls it and loops at `__end__`. The call is redirected to `entrypoint`,
+/// which must have the same signature so that the builtin pointers are passed and
+/// returned at the same place.
+pub fn set_proof_mode_entrypoint(
+    program: &mut serde_json::Value,
+    entrypoint: &str,
+) -> Result<(), EntrypointError> {
+    let identifiers = &program["identifiers"];
+    let function_pc = |name: &str| {
+        let function = &identifiers[format!("__main__.{name}")];
+        match function["type"].as_str() {
+            Some("function") => function["pc"].as_u64(),
+            _ => None,
+        }
+    };
+    let main_pc =
+        function_pc("main").ok_or_else(|| EntrypointError::MissingFunction("main".to_string()))?;
+    let entrypoint_pc = function_pc(entrypoint)
+        .ok_or_else(|| EntrypointError::MissingFunction(entrypoint.to_string()))?;
+    let start_pc = identifiers["__main__.__start__"]["pc"]
+        .as_u64()
+        .ok_or(EntrypointError::NotProofMode)?;
+
+    for member in ["Args", "ImplicitArgs", "Return"] {
+        let signature = |name: &str| {
+            let identifier = &identifiers[format!("__main__.{name}.{member}")];
+            (
+                identifier.get("members").cloned(),
+                identifier.get("cairo_type").cloned(),
+            )
+        };
+        if signature("main") != signature(entrypoint) {
+            return Err(EntrypointError::SignatureMismatch(entrypoint.to_string()));
+        }
+    }
+
+    // `__start__` is `ap += <size of the arguments>; call main;`
+    let call_pc = start_pc + 2;
+    let data = program["data"]
+        .as_array_mut()
+        .ok_or(EntrypointError::NotProofMode)?;
+    let word = |index: u64| {
+        data.get(index as usize)
+            .and_then(|word| word.as_str())
+            .and_then(|word| parse_felt(word).ok())
+    };
+    let main_offset = Felt252::from(main_pc as i64 - call_pc as i64);
+    if word(call_pc) != parse_felt(CALL_REL).ok() || word(call_pc + 1) != Some(main_offset) {
+        return Err(EntrypointError::NotProofMode);
+    }
+
+    let entrypoint_offset = Felt252::from(entrypoint_pc as i64 - call_pc as i64);
+    data[call_pc as usize + 1] = format!("{entrypoint_offset:#x}").into();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    /// The entry code of a proof mode program and three functions: `main` at pc 6,
+    /// `other` with the same signature at pc 10 and `mismatch` returning a felt at pc 14.
+    fn program() -> serde_json::Value {
+        let mut identifiers = serde_json::Map::new();
+        identifiers.insert(
+            "__main__.__start__".to_string(),
+            serde_json::json!({"type": "label", "pc": 0}),
+        );
+        for (name, pc, return_type) in [
+            ("main", 6, "(output_ptr: felt*)"),
+            ("other", 10, "(output_ptr: felt*)"),
+            ("mismatch", 14, "felt"),
+        ] {
+            let function = format!("__main__.{name}");
+            identifiers.insert(
+                function.clone(),
+                serde_json::json!({"type": "function", "pc": pc}),
+            );
+            identifiers.insert(
+                format!("{function}.Args"),
+                serde_json::json!({
+                    "full_name": format!("{function}.Args"),
+                    "members": {"output_ptr": {"cairo_type": "felt*", "offset": 0}},
+                    "size": 1,
+                    "type": "struct",
+                }),
+            );
+            identifiers.insert(
+                format!("{function}.ImplicitArgs"),
+                serde_json::json!({
+                    "full_name": format!("{function}.ImplicitArgs"),
+                    "members": {},
+                    "size": 0,
+                    "type": "struct",
+                }),
+            );
+            identifiers.insert(
+                format!("{function}.Return"),
+                serde_json::json!({"cairo_type": return_type, "type": "type_definition"}),
+            );
+        }
+
+        serde_json::json!({
+            "data": ["0x40780017fff7fff", "0x1", CALL_REL, "0x4", "0x10780017fff7fff", "0x0"],
+            "identifiers": identifiers,
+        })
+    }
+
+    #[test]
+    fn test_set_proof_mode_entrypoint() {
+        let mut program = program();
+        set_proof_mode_entrypoint(&mut program, "other").unwrap();
+        assert_eq!(program["data"][3], "0x8");
+    }
+
+    #[rstest]
+    #[case("missing", EntrypointError::MissingFunction("missing".to_string()))]
+    #[case("mismatch", EntrypointError::SignatureMismatch("mismatch".to_string()))]
+    fn test_set_proof_mode_entrypoint_errors(
+        #[case] entrypoint: &str,
+        #[case] expected: EntrypointError,
+    ) {
+        let mut program = program();
+        assert_eq!(
+            set_proof_mode_entrypoint(&mut program, entrypoint),
+            Err(expected)
+        );
+    }
+
+    #[test]
+    fn test_set_proof_mode_entrypoint_without_start() {
+        let mut program = program();
+        program["identifiers"]
+            .as_object_mut()
+            .unwrap()
+            .remove("__main__.__start__");
+        assert_eq!(
+            set_proof_mode_entrypoint(&mut program, "other"),
+            Err(EntrypointError::NotProofMode)
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use cairo_vm::Felt252;
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+use starknet_crypto::{pedersen_hash, poseidon_hash_many, FieldElement};
+
+use crate::cli::HashFunction;
+
+/// The subset of a compiled Cairo 0 program required to compute its hash.
+#[derive(Deserialize, Debug)]
+pub struct CompiledProgram {
+    pub data: Vec<String>,
+    pub builtins: Vec<String>,
+    pub identifiers: HashMap<String, Identifier>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Identifier {
+    pub pc: Option<u64>,
+}
+
+/// Describes how the output of a task is split in pages and how these pages are organized
+/// in a Merkle tree. Matches the entries of the fact topologies file written by the bootloader.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct FactTopology {
+    pub tree_structure: Vec<usize>,
+    pub page_sizes: Vec<usize>,
+}
+
+impl FactTopology {
+    /// A single page containing the whole output.
+    pub fn trivial(output_size: usize) -> Self {
+        Self {
+            tree_structure: vec![1, 0],
+            page_sizes: vec![output_size],
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct FactTopologiesFile {
+    pub fact_topologies: Vec<FactTopology>,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FactError {
+    #[error("the program does not have a {0} function")]
+    MissingEntrypoint(String),
+
+    #[error("invalid program data: {0}")]
+    InvalidProgramData(String),
+
+    #[error("invalid fact topology: {0}")]
+    InvalidFactTopology(String),
+}
+
+fn to_field_element(felt: &Felt252) -> FieldElement {
+    // Both types represent elements of the same field, this cannot fail
+    FieldElement::from_bytes_be(&felt.to_bytes_be()).unwrap()
+}
+
+fn to_felt(field_element: FieldElement) -> Felt252 {
+    Felt252::from_bytes_be(&field_element.to_bytes_be())
+}
+
+/// Interprets an ASCII string as a big-endian integer, like `from_bytes` in cairo-lang.
+fn felt_from_ascii(value: &str) -> Felt252 {
+    let bytes = value.as_bytes();
+    let mut buffer = [0u8; 32];
+    buffer[32 - bytes.len()..].copy_from_slice(bytes);
+    Felt252::from_bytes_be(&buffer)
+}
+
+/// Pedersen hash chain, as computed by `compute_hash_chain` in cairo-lang.
+fn compute_hash_chain(data: &[Felt252]) -> Felt252 {
+    let data: Vec<FieldElement> = data.iter().map(to_field_element).collect();
+    let hash = data
+        .iter()
+        .rev()
+        .copied()
+        .reduce(|acc, value| pedersen_hash(&value, &acc))
+        .unwrap_or(FieldElement::ZERO);
+    to_felt(hash)
+}
+
+/// Computes the hash of a program the same way as the bootloader does, see
+/// `compute_program_hash_chain` in cairo-lang. The program header written by the bootloader
+/// includes the offset of the entrypoint, so the hash depends on it.
+pub fn compute_program_hash(
+    program: &CompiledProgram,
+    hash_function: HashFunction,
+    entrypoint: &str,
+) -> Result<Felt252, FactError> {
+    const BOOTLOADER_VERSION: u64 = 0;
+
+    let main = program
+        .identifiers
+        .get(&format!("__main__.{entrypoint}"))
+        .and_then(|identifier| identifier.pc)
+        .ok_or_else(|| FactError::MissingEntrypoint(entrypoint.to_string()))?;
+
+    let mut data_chain = vec![
+        Felt252::from(BOOTLOADER_VERSION),
+        Felt252::from(main),
+        Felt252::from(program.builtins.len()),
+    ];
+    data_chain.extend(
+        program
+            .builtins
+            .iter()
+            .map(|builtin| felt_from_ascii(builtin.as_str())),
+    );
+    for value in &program.data {
+        let felt = Felt252::from_hex(value)
+            .map_err(|_| FactError::InvalidProgramData(value.to_string()))?;
+        data_chain.push(felt);
+    }
+
+    let program_hash = match hash_function {
+        HashFunction::Pedersen => {
+            let mut hash_chain = vec![Felt252::from(data_chain.len())];
+            hash_chain.extend(data_chain);
+            compute_hash_chain(&hash_chain)
+        }
+        HashFunction::Poseidon => {
+            let data_chain: Vec<FieldElement> = data_chain.iter().map(to_field_element).collect();
+            to_felt(poseidon_hash_many(&data_chain))
+        }
+    };
+
+    Ok(program_hash)
+}
+
+fn keccak_felts(felts: &[Felt252]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for felt in felts {
+        hasher.update(felt.to_bytes_be());
+    }
+    hasher.finalize().into()
+}
+
+/// Adds one to a 256-bit big-endian integer, wrapping around on overflow.
+fn increment(mut value: [u8; 32]) -> [u8; 32] {
+    for byte in value.iter_mut().rev() {
+        let (result, overflow) = byte.overflowing_add(1);
+        *byte = result;
+        if !overflow {
+            break;
+        }
+    }
+    value
+}
+
+struct FactNode {
+    hash: [u8; 32],
+    end_offset: usize,
+}
+
+/// Computes the root of the Merkle tree of output pages described by the fact topology,
+/// see `generate_output_root` in cairo-lang.
+pub fn compute_output_root(
+    output: &[Felt252],
+    fact_topology: &FactTopology,
+) -> Result<[u8; 32], FactError> {
+    let page_sizes = &fact_topology.page_sizes;
+    let total_size: usize = page_sizes.iter().sum();
+    if total_size != output.len() {
+        return Err(FactError::InvalidFactTopology(format!(
+            "the page sizes add up to {total_size} but the output has {} values",
+            output.len()
+        )));
+    }
+    let steps = fact_topology.tree_structure.chunks_exact(2);
+    if !steps.remainder().is_empty() {
+        return Err(FactError::InvalidFactTopology(
+            "the tree structure must have an even length".to_string(),
+        ));
+    }
+
+    let mut node_stack: Vec<FactNode> = vec![];
+    let mut end_offset = 0;
+    let mut next_page = 0;
+
+    for step in steps {
+        let (n_pages, n_nodes) = (step[0], step[1]);
+
+        for _ in 0..n_pages {
+            let page_size = *page_sizes.get(next_page).ok_or_else(|| {
+                FactError::InvalidFactTopology("the tree structure has too many pages".to_string())
+            })?;
+            let page = &output[end_offset..end_offset + page_size];
+            end_offset += page_size;
+            node_stack.push(FactNode {
+                hash: keccak_felts(page),
+                end_offset,
+            });
+            next_page += 1;
+        }
+
+        if n_nodes > 0 {
+            if n_nodes > node_stack.len() {
+                return Err(FactError::InvalidFactTopology(
+                    "the tree structure merges more nodes than available".to_string(),
+                ));
+            }
+            let children = node_stack.split_off(node_stack.len() - n_nodes);
+            let mut hasher = Keccak256::new();
+            for child in &children {
+                hasher.update(child.hash);
+                hasher.update(Felt252::from(child.end_offset).to_bytes_be());
+            }
+            node_stack.push(FactNode {
+                hash: increment(hasher.finalize().into()),
+                end_offset: children[children.len() - 1].end_offset,
+            });
+        }
+    }
+
+    if node_stack.len() != 1 || next_page != page_sizes.len() {
+        return Err(FactError::InvalidFactTopology(
+            "the tree structure does not reduce to a single root".to_string(),
+        ));
+    }
+
+    Ok(node_stack[0].hash)
+}
+
+/// Computes the fact registered in the L1 fact registry: `keccak(program_hash, output_root)`.
+pub fn compute_fact(program_hash: &Felt252, output_root: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(program_hash.to_bytes_be());
+    hasher.update(output_root);
+    hasher.finalize().into()
+}
+
+/// The output of a single task, as written by the bootloader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskOutput {
+    pub program_hash: Felt252,
+    pub output: Vec<Felt252>,
+}
+
+/// Splits the output of the bootloader in task outputs.
+///
+/// The bootloader output starts with the number of tasks, followed by
+/// `[output_size, program_hash, output...]` for each task, where `output_size`
+/// includes the two header values.
+pub fn parse_bootloader_output(output: &[Felt252]) -> Result<Vec<TaskOutput>, String> {
+    let to_usize = |felt: &Felt252| {
+        usize::try_from(felt.to_biguint()).map_err(|_| format!("{felt} is not a valid size"))
+    };
+
+    let (n_tasks, mut remaining) = output
+        .split_first()
+        .ok_or_else(|| "the output is empty".to_string())?;
+    let n_tasks = to_usize(n_tasks)?;
+
+    let mut task_outputs = Vec::with_capacity(n_tasks);
+    for task_index in 0..n_tasks {
+        let output_size = remaining
+            .first()
+            .map(to_usize)
+            .transpose()?
+            .ok_or_else(|| format!("missing output for task {task_index}"))?;
+        if !(2..=remaining.len()).contains(&output_size) {
+            return Err(format!("invalid output size for task {task_index}"));
+        }
+        task_outputs.push(TaskOutput {
+            program_hash: remaining[1],
+            output: remaining[2..output_size].to_vec(),
+        });
+        remaining = &remaining[output_size..];
+    }
+
+    if !remaining.is_empty() {
+        return Err(format!(
+            "{} unexpected values after the last task",
+            remaining.len()
+        ));
+    }
+
+    Ok(task_outputs)
+}
+
+/// Formats a 32-byte value as a 0x-prefixed hexadecimal string.
+pub fn to_hex(bytes: &[u8; 32]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    // The expected values below were computed with a standalone Python port of
+    // `generate_output_root` from cairo-lang (starkware/cairo/bootloaders/compute_fact.py).
+
+    #[test]
+    fn test_compute_output_root_trivial_topology() {
+        let output = vec![Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        let root = compute_output_root(&output, &FactTopology::trivial(output.len())).unwrap();
+        assert_eq!(
+            to_hex(&root),
+            "0x6e0c627900b24bd432fe7b1f713f1b0744091a646a9fe4a65a18dfed21f2949c"
+        );
+    }
+
+    #[test]
+    fn test_compute_output_root_two_pages() {
+        let output = vec![Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        let fact_topology = FactTopology {
+            tree_structure: vec![2, 2],
+            page_sizes: vec![1, 2],
+        };
+        let root = compute_output_root(&output, &fact_topology).unwrap();
+        assert_eq!(
+            to_hex(&root),
+            "0x419ce0d19575a71c2145e3c830b2e72b1e739b8a38648cca85b9aee4d57a78f1"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        FactTopology::trivial(3),
+        "0xaea03a44eee85d025be846e005073fa6b9990d3161e1fcded67075c67822bc30"
+    )]
+    #[case(
+        FactTopology { tree_structure: vec![2, 2], page_sizes: vec![1, 2] },
+        "0x06c1b44fb32ff79171875e8f8f1ea362d244edb4fa5bac429cab60948aaf197a"
+    )]
+    fn test_compute_fact(#[case] fact_topology: FactTopology, #[case] expected: &str) {
+        let output = vec![Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        let output_root = compute_output_root(&output, &fact_topology).unwrap();
+        let fact = compute_fact(&Felt252::from(0x1234), &output_root);
+        assert_eq!(to_hex(&fact), expected);
+    }
+
+    #[test]
+    fn test_compute_output_root_invalid_page_sizes() {
+        let output = vec![Felt252::from(1)];
+        assert!(matches!(
+            compute_output_root(&output, &FactTopology::trivial(2)),
+            Err(FactError::InvalidFactTopology(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_bootloader_output() {
+        let output: Vec<Felt252> = [2, 3, 100, 7, 4, 200, 8, 9]
+            .into_iter()
+            .map(Felt252::from)
+            .collect();
+        let task_outputs = parse_bootloader_output(&output).unwrap();
+
+        assert_eq!(
+            task_outputs,
+            vec![
+                TaskOutput {
+                    program_hash: Felt252::from(100),
+                    output: vec![Felt252::from(7)],
+                },
+                TaskOutput {
+                    program_hash: Felt252::from(200),
+                    output: vec![Felt252::from(8), Felt252::from(9)],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_increment() {
+        let mut value = [0u8; 32];
+        value[31] = 0xff;
+        let mut expected = [0u8; 32];
+        expected[30] = 1;
+        assert_eq!(increment(value), expected);
+    }
+}
@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+
+use cairo_vm::Felt252;
+
+use crate::cli::FeltEncoding;
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ParseFeltError {
+    #[error("invalid felt value: {0}")]
+    InvalidFelt(String),
+}
+
+/// Parses a felt from a decimal or 0x-prefixed hexadecimal string.
+/// Negative values are interpreted modulo the field prime.
+pub fn parse_felt(value: &str) -> Result<Felt252, ParseFeltError> {
+    let invalid_felt = |_| ParseFeltError::InvalidFelt(value.to_string());
+
+    if let Some(abs_value) = value.strip_prefix('-') {
+        return parse_felt(abs_value).map(|felt| -felt);
+    }
+
+    match value.strip_prefix("0x") {
+        Some(_) => Felt252::from_hex(value).map_err(invalid_felt),
+        None => Felt252::from_dec_str(value).map_err(invalid_felt),
+    }
+}
+
+/// Parses a list of felts separated by commas and/or whitespace.
+///
+/// Surrounding brackets and quotes are ignored, so JSON arrays of numbers or strings
+/// are accepted as well.
+pub fn parse_felt_list(values: &str) -> Result<Vec<Felt252>, ParseFeltError> {
+    values
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|value| value.trim_matches('"'))
+        .filter(|value| !value.is_empty())
+        .map(parse_felt)
+        .collect()
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoadFeltListError {
+    #[error("Failed to read file {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error(transparent)]
+    Parse(#[from] ParseFeltError),
+}
+
+/// Reads a list of felts from a file if `value` is an existing path,
+/// or parses it as an inline list of felts otherwise.
+pub fn load_felt_list(value: &str) -> Result<Vec<Felt252>, LoadFeltListError> {
+    let path = Path::new(value);
+    let felts = match path.is_file() {
+        true => {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| LoadFeltListError::Io(path.to_path_buf(), e))?;
+            parse_felt_list(&content)?
+        }
+        false => parse_felt_list(value)?,
+    };
+
+    Ok(felts)
+}
+
+/// Decodes a felt as a Cairo short string, i.e. up to 31 ASCII characters.
+fn decode_short_string(felt: &Felt252) -> Option<String> {
+    let bytes = felt.to_bytes_be();
+    let first_non_zero = bytes.iter().position(|byte| *byte != 0)?;
+    let bytes = &bytes[first_non_zero..];
+    if !bytes
+        .iter()
+        .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+    {
+        return None;
+    }
+    Some(String::from_utf8_lossy(bytes).to_string())
+}
+
+/// Formats a felt for display. Values that are not valid short strings are formatted
+/// as hexadecimal.
+pub fn format_felt(felt: &Felt252, encoding: FeltEncoding) -> String {
+    let value = felt.to_biguint();
+    match encoding {
+        FeltEncoding::Decimal => value.to_string(),
+        FeltEncoding::Hex => format!("{value:#x}"),
+        FeltEncoding::Signed => {
+            // Values above half the prime are negative
+            let negated_value = (-*felt).to_biguint();
+            match negated_value < value {
+                true => format!("-{negated_value}"),
+                false => value.to_string(),
+            }
+        }
+        FeltEncoding::ShortString => {
+            decode_short_string(felt).unwrap_or_else(|| format!("{value:#x}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("0", Felt252::ZERO)]
+    #[case("42", Felt252::from(42))]
+    #[case("0x2a", Felt252::from(42))]
+    #[case("-1", Felt252::ZERO - Felt252::ONE)]
+    fn test_parse_felt(#[case] value: &str, #[case] expected: Felt252) {
+        assert_eq!(parse_felt(value), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("1 2 3")]
+    #[case("1,2,3")]
+    #[case("[1, 2, 3]")]
+    #[case("[\"0x1\", \"0x2\", \"0x3\"]\n")]
+    fn test_parse_felt_list(#[case] values: &str) {
+        let expected = vec![Felt252::from(1), Felt252::from(2), Felt252::from(3)];
+        assert_eq!(parse_felt_list(values), Ok(expected));
+    }
+
+    #[rstest]
+    #[case(Felt252::from(42), FeltEncoding::Decimal, "42")]
+    #[case(Felt252::from(42), FeltEncoding::Hex, "0x2a")]
+    #[case(Felt252::from(42), FeltEncoding::Signed, "42")]
+    #[case(Felt252::ZERO - Felt252::from(42), FeltEncoding::Signed, "-42")]
+    #[case(Felt252::from_hex("0x48656c6c6f").unwrap(), FeltEncoding::ShortString, "Hello")]
+    #[case(Felt252::from(1), FeltEncoding::ShortString, "0x1")]
+    fn test_format_felt(
+        #[case] felt: Felt252,
+        #[case] encoding: FeltEncoding,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_felt(&felt, encoding), expected);
+    }
+
+    #[test]
+    fn test_parse_invalid_felt() {
+        assert_eq!(
+            parse_felt("abc"),
+            Err(ParseFeltError::InvalidFelt("abc".to_string()))
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+const ZIP_MAGIC_BYTES: &[u8; 4] = b"PK\x03\x04";
+
+/// The kinds of files that can be passed as tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskFileType {
+    CairoPieZip,
+    CairoPieDirectory,
+    Cairo0Program,
+    Sierra,
+    Casm,
+}
+
+impl Display for TaskFileType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::CairoPieZip => "Cairo PIE",
+            Self::CairoPieDirectory => "unzipped Cairo PIE",
+            Self::Cairo0Program => "compiled Cairo 0 program",
+            Self::Sierra => "Sierra program",
+            Self::Casm => "CASM program",
+        };
+        write!(f, "{description}")
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum FileTypeError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("unknown file type, tried: {}", .0.join("; "))]
+    Unknown(Vec<String>),
+}
+
+/// The top-level keys that identify the different program formats.
+#[derive(Deserialize, Debug)]
+struct ProgramKeys {
+    // Cairo 0
+    data: Option<IgnoredAny>,
+    prime: Option<IgnoredAny>,
+    // Sierra, either as a contract class or as a bare program
+    sierra_program: Option<IgnoredAny>,
+    type_declarations: Option<IgnoredAny>,
+    // CASM
+    bytecode: Option<IgnoredAny>,
+}
+
+/// Detects the type of a task file from its content rather than from its extension.
+pub fn detect_task_file_type(path: &Path) -> Result<TaskFileType, FileTypeError> {
+    let mut tried = vec![];
+
+    if path.is_dir() {
+        if path.join("metadata.json").is_file() {
+            return Ok(TaskFileType::CairoPieDirectory);
+        }
+        tried.push(format!(
+            "{}: the directory does not contain metadata.json",
+            TaskFileType::CairoPieDirectory
+        ));
+        return Err(FileTypeError::Unknown(tried));
+    }
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = Vec::with_capacity(ZIP_MAGIC_BYTES.len());
+    (&mut reader)
+        .take(ZIP_MAGIC_BYTES.len() as u64)
+        .read_to_end(&mut header)?;
+    if header == ZIP_MAGIC_BYTES {
+        return Ok(TaskFileType::CairoPieZip);
+    }
+    tried.push(format!("{}: not a zip file", TaskFileType::CairoPieZip));
+
+    let content = header.as_slice().chain(reader);
+    let keys: ProgramKeys = match serde_json::from_reader(content) {
+        Ok(keys) => keys,
+        Err(e) => {
+            tried.push(format!("compiled program: not a JSON object ({e})"));
+            return Err(FileTypeError::Unknown(tried));
+        }
+    };
+
+    if keys.bytecode.is_some() {
+        return Ok(TaskFileType::Casm);
+    }
+    if keys.sierra_program.is_some() || keys.type_declarations.is_some() {
+        return Ok(TaskFileType::Sierra);
+    }
+    if keys.data.is_some() && keys.prime.is_some() {
+        return Ok(TaskFileType::Cairo0Program);
+    }
+    tried.push(format!(
+        "{}: missing `data` or `prime`",
+        TaskFileType::Cairo0Program
+    ));
+    tried.push(format!(
+        "{}/{}: missing `sierra_program`, `type_declarations` or `bytecode`",
+        TaskFileType::Sierra,
+        TaskFileType::Casm
+    ));
+
+    Err(FileTypeError::Unknown(tried))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(b"PK\x03\x04\x14\x00".as_slice(), TaskFileType::CairoPieZip)]
+    #[case(br#"{"data": [], "prime": "0x1", "builtins": []}"#.as_slice(), TaskFileType::Cairo0Program)]
+    #[case(br#"{"sierra_program": [], "entry_points_by_type": {}}"#.as_slice(), TaskFileType::Sierra)]
+    #[case(br#"{"prime": "0x1", "bytecode": []}"#.as_slice(), TaskFileType::Casm)]
+    fn test_detect_task_file_type(#[case] content: &[u8], #[case] expected: TaskFileType) {
+        let dir = tempfile::tempdir().unwrap();
+        // The extension must not matter
+        let file = dir.path().join("task.bin");
+        std::fs::write(&file, content).unwrap();
+
+        assert_eq!(detect_task_file_type(&file).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_detect_cairo_pie_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("metadata.json"), "{}").unwrap();
+
+        assert_eq!(
+            detect_task_file_type(dir.path()).unwrap(),
+            TaskFileType::CairoPieDirectory
+        );
+    }
+
+    #[rstest]
+    #[case(b"abc".as_slice())]
+    #[case(br#"{"key": "value"}"#.as_slice())]
+    fn test_detect_unknown_file_type(#[case] content: &[u8]) {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("task.json");
+        std::fs::write(&file, content).unwrap();
+
+        assert!(matches!(
+            detect_task_file_type(&file),
+            Err(FileTypeError::Unknown(tried)) if tried.len() >= 2
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::fs::File;
 use std::path::Path;
 
@@ -19,3 +20,22 @@ pub fn read_json_from_file<T: DeserializeOwned, P: AsRef<Path>>(
     let obj: T = serde_json::from_reader(&mut reader)?;
     Ok(obj)
 }
+
+pub fn write_json_to_file<T: Serialize, P: AsRef<Path>>(
+    obj: T,
+    path: P,
+) -> Result<(), std::io::Error> {
+    let mut file = File::create(path)?;
+    serde_json::to_writer(&mut file, &obj)?;
+    Ok(())
+}
+
+/// Writes `obj` as indented JSON, for files meant to be read and edited by users.
+pub fn write_pretty_json_to_file<T: Serialize, P: AsRef<Path>>(
+    obj: T,
+    path: P,
+) -> Result<(), std::io::Error> {
+    let mut file = File::create(path)?;
+    serde_json::to_writer_pretty(&mut file, &obj)?;
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use stone_prover_sdk::models::{Layout, Verifier};
+
+/// Describes how a builtin is laid out in the trace of a layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinSpec {
+    pub name: &'static str,
+    /// Number of steps per builtin component. `None` for builtins that do not depend on
+    /// the trace length, i.e. the output builtin.
+    pub ratio: Option<u32>,
+    pub instances_per_component: u32,
+    pub cells_per_instance: u32,
+}
+
+const fn builtin(name: &'static str, ratio: u32, cells_per_instance: u32) -> BuiltinSpec {
+    BuiltinSpec {
+        name,
+        ratio: Some(ratio),
+        instances_per_component: 1,
+        cells_per_instance,
+    }
+}
+
+const OUTPUT: BuiltinSpec = BuiltinSpec {
+    name: "output",
+    ratio: None,
+    instances_per_component: 1,
+    cells_per_instance: 1,
+};
+
+const KECCAK: BuiltinSpec = BuiltinSpec {
+    name: "keccak",
+    ratio: Some(2048),
+    instances_per_component: 16,
+    cells_per_instance: 16,
+};
+
+/// Layouts considered when no layout is specified. Ties on the size of the trace go to
+/// the first layout.
+pub const AUTO_LAYOUT_CANDIDATES: [Layout; 6] = [
+    Layout::Plain,
+    Layout::Small,
+    Layout::Recursive,
+    Layout::RecursiveLargeOutput,
+    Layout::Starknet,
+    Layout::StarknetWithKeccak,
+];
+
+/// Layout used when the resources of a program must be measured before choosing its layout.
+/// It includes all the builtins.
+pub const PROBE_LAYOUT: Layout = Layout::StarknetWithKeccak;
+
+/// Returns the builtins of a layout, as defined in cairo-lang, or `None` if the layout is
+/// not supported by this module.
+pub fn layout_builtins(layout: Layout) -> Option<&'static [BuiltinSpec]> {
+    const SMALL: &[BuiltinSpec] = &[
+        OUTPUT,
+        builtin("pedersen", 8, 3),
+        builtin("range_check", 8, 1),
+        builtin("ecdsa", 512, 2),
+    ];
+    const RECURSIVE: &[BuiltinSpec] = &[
+        OUTPUT,
+        builtin("pedersen", 128, 3),
+        builtin("range_check", 8, 1),
+        builtin("bitwise", 8, 5),
+    ];
+    const RECURSIVE_LARGE_OUTPUT: &[BuiltinSpec] = &[
+        OUTPUT,
+        builtin("pedersen", 128, 3),
+        builtin("range_check", 8, 1),
+        builtin("bitwise", 8, 5),
+        builtin("poseidon", 8, 6),
+    ];
+    const STARKNET: &[BuiltinSpec] = &[
+        OUTPUT,
+        builtin("pedersen", 32, 3),
+        builtin("range_check", 16, 1),
+        builtin("ecdsa", 2048, 2),
+        builtin("bitwise", 64, 5),
+        builtin("ec_op", 1024, 7),
+        builtin("poseidon", 32, 6),
+    ];
+    const STARKNET_WITH_KECCAK: &[BuiltinSpec] = &[
+        OUTPUT,
+        builtin("pedersen", 32, 3),
+        builtin("range_check", 16, 1),
+        builtin("ecdsa", 2048, 2),
+        builtin("bitwise", 64, 5),
+        builtin("ec_op", 1024, 7),
+        KECCAK,
+        builtin("poseidon", 32, 6),
+    ];
+
+    match layout {
+        Layout::Plain => Some(&[]),
+        Layout::Small => Some(SMALL),
+        Layout::Recursive => Some(RECURSIVE),
+        Layout::RecursiveLargeOutput => Some(RECURSIVE_LARGE_OUTPUT),
+        Layout::Starknet => Some(STARKNET),
+        Layout::StarknetWithKeccak => Some(STARKNET_WITH_KECCAK),
+        _ => None,
+    }
+}
+
+/// Trace rows per step, the same for all the candidate layouts.
+const CPU_COMPONENT_HEIGHT: usize = 16;
+
+/// Returns the number of trace columns of a layout in the Stone AIR, or `None` if the
+/// layout is not supported by this module.
+pub fn layout_columns(layout: Layout) -> Option<usize> {
+    match layout {
+        Layout::Plain => Some(8),
+        Layout::Small => Some(25),
+        Layout::Recursive => Some(10),
+        Layout::RecursiveLargeOutput => Some(13),
+        Layout::Starknet => Some(10),
+        Layout::StarknetWithKeccak => Some(15),
+        _ => None,
+    }
+}
+
+/// Returns whether proofs in the layout can be checked by the verifier. The Starknet L1
+/// verifier contracts are not deployed for the plain and recursive_large_output layouts.
+pub fn is_verifiable(layout: Layout, verifier: &Verifier) -> bool {
+    match verifier {
+        Verifier::Stone => true,
+        Verifier::L1 => !matches!(layout, Layout::Plain | Layout::RecursiveLargeOutput),
+    }
+}
+
+/// Returns the description of a builtin, independently of any layout ratio.
+pub fn builtin_spec(name: &str) -> Option<&'static BuiltinSpec> {
+    // This layout includes all the builtins
+    layout_builtins(Layout::StarknetWithKeccak)
+        .unwrap()
+        .iter()
+        .find(|spec| spec.name == name)
+}
+
+/// Steps and builtin instances used by an execution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    pub n_steps: usize,
+    /// Number of instances of each builtin, by builtin name (without the `_builtin` suffix).
+    /// Declared builtins that are not used have 0 instances.
+    pub builtin_instances: BTreeMap<String, usize>,
+}
+
+impl ResourceUsage {
+    pub fn from_execution_resources(execution_resources: &ExecutionResources) -> Self {
+        let builtin_instances = execution_resources
+            .builtin_instance_counter
+            .iter()
+            .map(|(name, count)| {
+                let name = name.to_string();
+                (name.trim_end_matches("_builtin").to_string(), *count)
+            })
+            .collect();
+
+        Self {
+            n_steps: execution_resources.n_steps,
+            builtin_instances,
+        }
+    }
+
+    /// Accumulates the resources of another execution, e.g. of another bootloader task.
+    pub fn add(&mut self, other: &ResourceUsage) {
+        self.n_steps += other.n_steps;
+        for (name, count) in &other.builtin_instances {
+            *self.builtin_instances.entry(name.clone()).or_default() += count;
+        }
+    }
+
+    /// Returns the builtins that a layout must provide: all the declared builtins, or only
+    /// the ones actually used if missing builtins are allowed.
+    pub fn required_builtins(&self, allow_missing_builtins: bool) -> BTreeSet<String> {
+        self.builtin_instances
+            .iter()
+            .filter(|(_, count)| !allow_missing_builtins || **count > 0)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+/// Returns the trace length required to fit the execution in the layout, or the builtins
+/// missing from the layout.
+pub fn required_trace_length(
+    layout_builtins: &[BuiltinSpec],
+    usage: &ResourceUsage,
+    required_builtins: &BTreeSet<String>,
+) -> Result<usize, Vec<String>> {
+    let missing_builtins: Vec<String> = required_builtins
+        .iter()
+        .filter(|name| !layout_builtins.iter().any(|spec| spec.name == *name))
+        .cloned()
+        .collect();
+    if !missing_builtins.is_empty() {
+        return Err(missing_builtins);
+    }
+
+    let builtin_steps = layout_builtins.iter().filter_map(|spec| {
+        let ratio = spec.ratio? as usize;
+        let instances = *usage.builtin_instances.get(spec.name)?;
+        let components = instances.div_ceil(spec.instances_per_component as usize);
+        Some(components * ratio)
+    });
+    let min_steps = builtin_steps.fold(usage.n_steps, usize::max);
+
+    Ok(min_steps.next_power_of_two())
+}
+
+/// The layout chosen for an execution.
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutSelection {
+    pub layout: Layout,
+    pub trace_length: usize,
+    /// Number of cells of the trace, i.e. rows times columns. The cost of the proof
+    /// grows with it.
+    pub trace_cells: usize,
+}
+
+/// Selects the layout that supports the required builtins with the fewest trace cells,
+/// among the layouts that the verifier can check. Returns `None` if no candidate layout
+/// fits.
+pub fn select_layout(
+    usage: &ResourceUsage,
+    required_builtins: &BTreeSet<String>,
+    verifier: &Verifier,
+) -> Option<LayoutSelection> {
+    let mut selection: Option<LayoutSelection> = None;
+    for layout in AUTO_LAYOUT_CANDIDATES {
+        if !is_verifiable(layout, verifier) {
+            log::debug!("layout {layout}: not supported by the {verifier:?} verifier.");
+            continue;
+        }
+        // Candidates are all supported
+        let builtins = layout_builtins(layout).unwrap();
+        match required_trace_length(builtins, usage, required_builtins) {
+            Ok(trace_length) => {
+                let trace_cells =
+                    trace_length * CPU_COMPONENT_HEIGHT * layout_columns(layout).unwrap();
+                log::debug!(
+                    "layout {layout}: trace length of {trace_length} steps, {trace_cells} cells."
+                );
+                if selection.map_or(true, |selection| trace_cells < selection.trace_cells) {
+                    selection = Some(LayoutSelection {
+                        layout,
+                        trace_length,
+                        trace_cells,
+                    });
+                }
+            }
+            Err(missing_builtins) => {
+                log::debug!(
+                    "layout {layout}: missing builtins {}.",
+                    missing_builtins.join(", ")
+                );
+            }
+        }
+    }
+
+    selection
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(n_steps: usize, builtin_instances: &[(&str, usize)]) -> ResourceUsage {
+        ResourceUsage {
+            n_steps,
+            builtin_instances: builtin_instances
+                .iter()
+                .map(|(name, count)| (name.to_string(), *count))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_select_layout_without_builtins() {
+        let usage = usage(1000, &[]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::Plain));
+        assert_eq!(selection.trace_length, 1024);
+        assert_eq!(selection.trace_cells, 1024 * 16 * 8);
+    }
+
+    #[test]
+    fn test_select_layout_for_l1_verifier() {
+        // There is no L1 verifier for the plain layout
+        let usage = usage(1000, &[]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::L1).unwrap();
+        assert!(matches!(selection.layout, Layout::Recursive));
+    }
+
+    #[test]
+    fn test_select_layout_with_output() {
+        // Both layouts fit the execution in 1024 steps, the small layout has more columns
+        let usage = usage(1000, &[("output", 2), ("range_check", 10)]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::Recursive));
+        assert_eq!(selection.trace_length, 1024);
+    }
+
+    #[test]
+    fn test_select_layout_fits_builtins() {
+        // 100 Pedersen hashes need 3200 steps with the starknet layout
+        // and 12800 steps with the recursive layouts
+        let usage = usage(1000, &[("pedersen", 100), ("bitwise", 1)]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::Starknet));
+        assert_eq!(selection.trace_length, 4096);
+    }
+
+    #[test]
+    fn test_select_layout_by_trace_cells() {
+        // 1000 ECDSA signatures need 2^19 steps with the small layout and 2^21 steps with
+        // the starknet layouts: the small layout has the fewest cells despite its columns
+        let usage = usage(1000, &[("ecdsa", 1000)]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::Small));
+        assert_eq!(selection.trace_length, 1 << 19);
+    }
+
+    #[test]
+    fn test_select_layout_ignores_unused_builtins() {
+        let usage = usage(1000, &[("output", 1), ("keccak", 0)]);
+        let selection =
+            select_layout(&usage, &usage.required_builtins(false), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::StarknetWithKeccak));
+
+        let selection =
+            select_layout(&usage, &usage.required_builtins(true), &Verifier::Stone).unwrap();
+        assert!(matches!(selection.layout, Layout::Recursive));
+    }
+}
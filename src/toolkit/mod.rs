@@ -1 +1,14 @@
+pub mod cairo_pie;
+pub mod entrypoint;
+pub mod fact;
+pub mod felt;
+pub mod file_type;
 pub mod json;
+pub mod layout;
+pub mod output;
+pub mod profile;
+pub mod program_cache;
+pub mod program_input;
+pub mod resources;
+pub mod sierra;
+pub mod tasks;
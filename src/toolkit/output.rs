@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use cairo_vm::Felt252;
+use serde::Serialize;
+use stone_prover_sdk::models::PublicInput;
+
+use crate::cli::FeltEncoding;
+use crate::toolkit::fact::parse_bootloader_output;
+use crate::toolkit::felt::format_felt;
+
+#[derive(thiserror::Error, Debug)]
+pub enum OutputError {
+    #[error("the program does not have an output segment")]
+    MissingOutputSegment,
+
+    #[error("memory cell {0} of the output segment is not part of the public memory")]
+    MissingPublicMemoryCell(u64),
+
+    #[error("invalid value for memory cell {0}: {1}")]
+    InvalidValue(u64, String),
+
+    #[error("invalid bootloader output: {0}")]
+    InvalidBootloaderOutput(String),
+}
+
+/// Reads the program output from the output builtin segment of the public input.
+pub fn read_program_output(public_input: &PublicInput) -> Result<Vec<Felt252>, OutputError> {
+    let output_segment = public_input
+        .memory_segments
+        .get("output")
+        .ok_or(OutputError::MissingOutputSegment)?;
+    let begin_addr = output_segment.begin_addr as u64;
+    let stop_ptr = output_segment.stop_ptr as u64;
+
+    let public_memory: HashMap<u64, &str> = public_input
+        .public_memory
+        .iter()
+        .map(|entry| (entry.address as u64, entry.value.as_str()))
+        .collect();
+
+    (begin_addr..stop_ptr)
+        .map(|address| {
+            let value = public_memory
+                .get(&address)
+                .ok_or(OutputError::MissingPublicMemoryCell(address))?;
+            Felt252::from_hex(value)
+                .map_err(|_| OutputError::InvalidValue(address, value.to_string()))
+        })
+        .collect()
+}
+
+/// The output of a bootloader task, formatted for display.
+#[derive(Serialize, Debug)]
+pub struct TaskOutputValues {
+    pub program_hash: String,
+    pub output: Vec<String>,
+}
+
+/// The output of a program or, in bootloader mode, of each task, formatted for display.
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+pub enum OutputValues {
+    Program(Vec<String>),
+    Tasks(Vec<TaskOutputValues>),
+}
+
+impl OutputValues {
+    pub fn new(
+        public_input: &PublicInput,
+        with_bootloader: bool,
+        encoding: FeltEncoding,
+    ) -> Result<Self, OutputError> {
+        let output = read_program_output(public_input)?;
+        let format_output = |output: &[Felt252]| {
+            output
+                .iter()
+                .map(|felt| format_felt(felt, encoding))
+                .collect()
+        };
+
+        if !with_bootloader {
+            return Ok(Self::Program(format_output(&output)));
+        }
+
+        let task_outputs =
+            parse_bootloader_output(&output).map_err(OutputError::InvalidBootloaderOutput)?;
+        let tasks = task_outputs
+            .iter()
+            .map(|task_output| TaskOutputValues {
+                program_hash: format_felt(&task_output.program_hash, FeltEncoding::Hex),
+                output: format_output(&task_output.output),
+            })
+            .collect();
+        Ok(Self::Tasks(tasks))
+    }
+
+    pub fn print(&self) {
+        match self {
+            Self::Program(output) => {
+                println!("Program output:");
+                for value in output {
+                    println!("  {value}");
+                }
+            }
+            Self::Tasks(tasks) => {
+                for (index, task) in tasks.iter().enumerate() {
+                    println!("Task {index} output (program hash {}):", task.program_hash);
+                    for value in &task.output {
+                        println!("  {value}");
+                    }
+                }
+            }
+        }
+    }
+}
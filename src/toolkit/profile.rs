@@ -0,0 +1,318 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use cairo_vm::types::program::Program;
+use stone_prover_sdk::cairo_vm::ExecutionArtifacts;
+
+use crate::toolkit::layout::builtin_spec;
+use crate::toolkit::resources::{
+    builtin_cells, count_padding_steps, read_trace, read_u64, TraceEntry, MEMORY_CELL_SIZE,
+};
+
+/// Name of the frame of the steps that do not belong to any function, e.g. `__start__`.
+const UNKNOWN_FUNCTION: &str = "<unknown>";
+
+/// Bias of the 16-bit offsets of an instruction.
+const OFFSET_BIAS: i64 = 1 << 15;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ProfileError {
+    #[error("the public input has no program segment")]
+    MissingProgramSegment,
+}
+
+/// Maps program counters to the functions of a program.
+#[derive(Debug, Default)]
+pub struct FunctionTable {
+    /// Start pc and name of each function, sorted by pc.
+    functions: Vec<(usize, String)>,
+}
+
+impl FunctionTable {
+    pub fn new(mut functions: Vec<(usize, String)>) -> Self {
+        functions.sort();
+        Self { functions }
+    }
+
+    /// Reads the functions from the identifiers of a compiled Cairo 0 program.
+    pub fn from_program(program: &Program) -> Self {
+        let functions = program
+            .iter_identifiers()
+            .filter(|(_, identifier)| identifier.type_.as_deref() == Some("function"))
+            .filter_map(|(name, identifier)| Some((identifier.pc?, name.to_string())))
+            .collect();
+        Self::new(functions)
+    }
+
+    /// Returns the function containing `pc`, i.e. the last function starting before it.
+    fn lookup(&self, pc: usize) -> &str {
+        let index = self.functions.partition_point(|(start, _)| *start <= pc);
+        match index {
+            0 => UNKNOWN_FUNCTION,
+            _ => &self.functions[index - 1].1,
+        }
+    }
+}
+
+/// Reads the memory, keeping the lower 8 bytes of each value. This is enough to decode
+/// instructions and addresses.
+fn read_memory(memory: &[u8]) -> HashMap<u64, u64> {
+    memory
+        .chunks_exact(MEMORY_CELL_SIZE)
+        .map(|cell| (read_u64(cell), read_u64(&cell[8..])))
+        .collect()
+}
+
+fn offset(instruction: u64, index: u32) -> i64 {
+    ((instruction >> (16 * index)) & 0xffff) as i64 - OFFSET_BIAS
+}
+
+/// Returns the addresses accessed by the instruction executed at a trace entry: the
+/// destination and the two operands. Immediate operands are not memory accesses.
+fn accessed_addresses(entry: &TraceEntry, memory: &HashMap<u64, u64>) -> Vec<u64> {
+    let Some(&instruction) = memory.get(&entry.pc) else {
+        return vec![];
+    };
+    let flags = instruction >> 48;
+    let register = |flag: u64| match flags & flag {
+        0 => entry.ap,
+        _ => entry.fp,
+    };
+    let address = |base: u64, offset: i64| base.checked_add_signed(offset);
+
+    let dst = address(register(1), offset(instruction, 0));
+    let op0 = address(register(1 << 1), offset(instruction, 1));
+    let op1_base = match (flags >> 2) & 0b111 {
+        0 => op0.and_then(|op0| memory.get(&op0).copied()),
+        // Immediate value
+        1 => None,
+        2 => Some(entry.fp),
+        4 => Some(entry.ap),
+        _ => None,
+    };
+    let op1 = op1_base.and_then(|base| address(base, offset(instruction, 2)));
+
+    [dst, op0, op1].into_iter().flatten().collect()
+}
+
+/// A memory segment used by a builtin.
+#[derive(Debug)]
+pub struct BuiltinSegment {
+    pub name: String,
+    pub begin_addr: u64,
+    pub stop_ptr: u64,
+    pub cells_per_instance: u64,
+}
+
+/// Steps and builtin instances of an execution, by call stack.
+///
+/// Call stacks are folded as in flamegraph tools: the functions from the outermost one,
+/// separated by semicolons.
+#[derive(Debug, Default, PartialEq)]
+pub struct ExecutionProfile {
+    pub steps: BTreeMap<String, usize>,
+    /// Instances of each builtin, by call stack.
+    pub builtins: BTreeMap<String, BTreeMap<String, usize>>,
+}
+
+impl ExecutionProfile {
+    /// Profiles the execution of a Cairo 0 program run in proof mode.
+    pub fn new(
+        program: &Program,
+        execution_artifacts: &ExecutionArtifacts,
+    ) -> Result<Self, ProfileError> {
+        let public_input = &execution_artifacts.public_input;
+        let program_base = public_input
+            .memory_segments
+            .get("program")
+            .ok_or(ProfileError::MissingProgramSegment)?
+            .begin_addr;
+        let builtin_segments: Vec<BuiltinSegment> = builtin_cells(public_input)
+            .filter_map(|(name, _)| {
+                let segment = &public_input.memory_segments[name];
+                Some(BuiltinSegment {
+                    name: name.to_string(),
+                    begin_addr: segment.begin_addr,
+                    stop_ptr: segment.stop_ptr,
+                    cells_per_instance: builtin_spec(name)?.cells_per_instance as u64,
+                })
+            })
+            .collect();
+
+        // The padding steps at the end of the trace do not belong to the program
+        let mut trace = read_trace(&execution_artifacts.trace);
+        trace.truncate(
+            trace
+                .len()
+                .saturating_sub(count_padding_steps(&execution_artifacts.trace)),
+        );
+
+        Ok(Self::from_trace(
+            &FunctionTable::from_program(program),
+            program_base,
+            &trace,
+            &read_memory(&execution_artifacts.memory),
+            &builtin_segments,
+        ))
+    }
+
+    /// Attributes each step to the call stack at that step, and each builtin instance to
+    /// the call stack of the first step that accesses one of its cells. Calls are detected
+    /// from the frame pointer: a new frame pointer is a call, a previous one a return.
+    fn from_trace(
+        functions: &FunctionTable,
+        program_base: u64,
+        trace: &[TraceEntry],
+        memory: &HashMap<u64, u64>,
+        builtin_segments: &[BuiltinSegment],
+    ) -> Self {
+        let mut profile = Self::default();
+        let mut frames: Vec<(u64, &str)> = vec![];
+        let mut used_instances: HashSet<(&str, u64)> = HashSet::new();
+
+        for entry in trace {
+            let function = functions.lookup(entry.pc.saturating_sub(program_base) as usize);
+            match frames.iter().rposition(|(fp, _)| *fp == entry.fp) {
+                Some(index) => frames.truncate(index + 1),
+                None => frames.push((entry.fp, function)),
+            }
+            let stack = frames
+                .iter()
+                .map(|(_, function)| *function)
+                .collect::<Vec<_>>()
+                .join(";");
+
+            for address in accessed_addresses(entry, memory) {
+                let Some(segment) = builtin_segments
+                    .iter()
+                    .find(|segment| (segment.begin_addr..segment.stop_ptr).contains(&address))
+                else {
+                    continue;
+                };
+                let instance = (address - segment.begin_addr) / segment.cells_per_instance;
+                if used_instances.insert((segment.name.as_str(), instance)) {
+                    *profile
+                        .builtins
+                        .entry(segment.name.clone())
+                        .or_default()
+                        .entry(stack.clone())
+                        .or_default() += 1;
+                }
+            }
+            *profile.steps.entry(stack).or_default() += 1;
+        }
+
+        profile
+    }
+
+    /// Writes the steps as folded stacks to `path`, and the instances of each builtin
+    /// to a file next to it, e.g. `profile.pedersen.folded` for `profile.folded`.
+    pub fn write_folded(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = vec![path.to_path_buf()];
+        write_folded_stacks(&self.steps, path)?;
+        for (builtin, stacks) in &self.builtins {
+            let builtin_path = builtin_profile_path(path, builtin);
+            write_folded_stacks(stacks, &builtin_path)?;
+            files.push(builtin_path);
+        }
+        Ok(files)
+    }
+}
+
+fn write_folded_stacks(stacks: &BTreeMap<String, usize>, path: &Path) -> std::io::Result<()> {
+    let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    for (stack, count) in stacks {
+        writeln!(file, "{stack} {count}")?;
+    }
+    file.flush()
+}
+
+/// Inserts the builtin name before the extension of the profile file.
+fn builtin_profile_path(path: &Path, builtin: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{stem}.{builtin}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{builtin}"),
+    };
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ap: u64, fp: u64, pc: u64) -> TraceEntry {
+        TraceEntry { ap, fp, pc }
+    }
+
+    /// Encodes `[ap + 0] = [fp + offset]`, i.e. an assert_eq with op1 read from fp.
+    fn assert_eq_from_fp(offset: i64) -> u64 {
+        let off0 = OFFSET_BIAS as u64;
+        let off1 = (OFFSET_BIAS - 1) as u64;
+        let off2 = (OFFSET_BIAS + offset) as u64;
+        // op0_reg = fp, op1_src = fp, opcode = assert_eq
+        let flags: u64 = (1 << 1) | (2 << 2) | (4 << 12);
+        off0 | (off1 << 16) | (off2 << 32) | (flags << 48)
+    }
+
+    #[test]
+    fn test_profile_call_stacks_and_builtins() {
+        let functions = FunctionTable::new(vec![
+            (0, "__main__.main".to_string()),
+            (10, "__main__.hash".to_string()),
+        ]);
+        let program_base = 1;
+        // main runs at fp 100, calls hash at fp 200 which reads a pedersen cell,
+        // then returns to main.
+        let trace = [
+            entry(100, 100, 1),
+            entry(100, 100, 2),
+            entry(200, 200, 11),
+            entry(201, 200, 12),
+            entry(202, 100, 3),
+        ];
+        let memory = HashMap::from([(12, assert_eq_from_fp(302))]);
+        let builtin_segments = [BuiltinSegment {
+            name: "pedersen".to_string(),
+            begin_addr: 500,
+            stop_ptr: 503,
+            cells_per_instance: 3,
+        }];
+
+        let profile = ExecutionProfile::from_trace(
+            &functions,
+            program_base,
+            &trace,
+            &memory,
+            &builtin_segments,
+        );
+
+        assert_eq!(
+            profile.steps,
+            BTreeMap::from([
+                ("__main__.main".to_string(), 3),
+                ("__main__.main;__main__.hash".to_string(), 2),
+            ])
+        );
+        assert_eq!(
+            profile.builtins,
+            BTreeMap::from([(
+                "pedersen".to_string(),
+                BTreeMap::from([("__main__.main;__main__.hash".to_string(), 1)])
+            )])
+        );
+    }
+
+    #[test]
+    fn test_builtin_profile_path() {
+        assert_eq!(
+            builtin_profile_path(Path::new("out/profile.folded"), "pedersen"),
+            PathBuf::from("out/profile.pedersen.folded")
+        );
+        assert_eq!(
+            builtin_profile_path(Path::new("profile"), "output"),
+            PathBuf::from("profile.output")
+        );
+    }
+}
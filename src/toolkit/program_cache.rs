@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use cairo_vm::types::errors::program_errors::ProgramError;
+use cairo_vm::types::program::Program;
+
+/// Programs loaded by this process, keyed by their source and entrypoint. Batch jobs
+/// often share programs and always share the bootloader, which is slow to parse.
+static PROGRAMS: OnceLock<Mutex<HashMap<(String, String), Program>>> = OnceLock::new();
+
+/// Returns the program cached for `source` and `entrypoint`, loading it with `load` the
+/// first time. The lock is not held while loading, concurrent jobs may parse the same
+/// program twice but never wait on each other.
+pub fn cached_program<E>(
+    source: &str,
+    entrypoint: &str,
+    load: impl FnOnce() -> Result<Program, E>,
+) -> Result<Program, E> {
+    let programs = PROGRAMS.get_or_init(Default::default);
+    let key = (source.to_string(), entrypoint.to_string());
+    if let Some(program) = programs.lock().unwrap().get(&key) {
+        return Ok(program.clone());
+    }
+
+    let program = load()?;
+    programs.lock().unwrap().insert(key, program.clone());
+    Ok(program)
+}
+
+/// Loads a compiled Cairo 0 program, parsing each file at most once.
+pub fn load_program(path: &Path, entrypoint: &str) -> Result<Program, ProgramError> {
+    cached_program(&path.to_string_lossy(), entrypoint, || {
+        Program::from_file(path, Some(entrypoint))
+    })
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use cairo_vm::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::HintFunc;
+use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::insert_value_from_var_name;
+use cairo_vm::hint_processor::hint_processor_definition::HintReference;
+use cairo_vm::serde::deserialize_program::ApTracking;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::types::relocatable::MaybeRelocatable;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+
+use crate::toolkit::felt::parse_felt;
+
+/// Name of the hint variable holding the program input, as in cairo-lang.
+pub const PROGRAM_INPUT: &str = "program_input";
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ProgramInputError {
+    #[error("hint `{0}` reads the program input, only hints made of `ids.<name> = <expression>` lines are supported, where the expression is `program_input[...]`, `len(program_input[...])` or `segments.gen_arg(program_input[...])`")]
+    UnsupportedHint(String),
+
+    #[error("the program input has no `{0}` key")]
+    MissingKey(String),
+
+    #[error("the value of `{0}` in the program input is not {1}")]
+    InvalidValue(String, &'static str),
+}
+
+/// The program input of a run, and the hints of the program that read it.
+///
+/// The Cairo VM only runs the hints of the Cairo libraries, none of which reads the program
+/// input. Hints that assign values of the program input to variables are run by the CLI
+/// itself, other hints reading the program input are rejected before the run.
+#[derive(Clone)]
+pub struct LoadedProgramInput {
+    pub hints: HashMap<String, Rc<HintFunc>>,
+}
+
+impl LoadedProgramInput {
+    /// Resolves the hints of `program`, a compiled Cairo 0 program, that read `value`.
+    pub fn new(
+        value: serde_json::Value,
+        program: &serde_json::Value,
+    ) -> Result<Self, ProgramInputError> {
+        let mut hints = HashMap::new();
+        for code in hint_codes(program) {
+            if !code.contains(PROGRAM_INPUT) || hints.contains_key(code) {
+                continue;
+            }
+            let assignments = parse_assignments(code)?
+                .into_iter()
+                .map(|(name, expression)| Ok((name.to_string(), expression.resolve(&value)?)))
+                .collect::<Result<Vec<_>, ProgramInputError>>()?;
+            hints.insert(code.to_string(), assignment_hint(assignments));
+        }
+
+        Ok(Self { hints })
+    }
+}
+
+/// Returns the code of the hints of a compiled Cairo 0 program.
+fn hint_codes(program: &serde_json::Value) -> impl Iterator<Item = &str> {
+    program["hints"]
+        .as_object()
+        .into_iter()
+        .flat_map(|hints| hints.values())
+        .filter_map(|pc_hints| pc_hints.as_array())
+        .flatten()
+        .filter_map(|hint| hint["code"].as_str())
+}
+
+/// Selects a value nested in the program input: a key of an object or an index of a list.
+#[derive(Debug, PartialEq)]
+enum Accessor<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Right-hand side of an assignment reading the program input.
+#[derive(Debug, PartialEq)]
+enum Expression<'a> {
+    /// `program_input[...]`, a felt.
+    Value(Vec<Accessor<'a>>),
+    /// `len(program_input[...])`, the length of a list.
+    Len(Vec<Accessor<'a>>),
+    /// `segments.gen_arg(program_input[...])`, a felt or a list written to a new segment.
+    GenArg(Vec<Accessor<'a>>),
+}
+
+/// A value of the program input, as written to memory.
+#[derive(Debug, Clone, PartialEq)]
+enum InputValue {
+    Felt(Felt252),
+    /// Written to a new segment. Nested lists are written to their own segments and
+    /// referenced by pointers, like with `segments.gen_arg` in cairo-lang.
+    List(Vec<InputValue>),
+}
+
+impl Expression<'_> {
+    /// Reads the value of the expression from the program input.
+    fn resolve(&self, program_input: &serde_json::Value) -> Result<InputValue, ProgramInputError> {
+        let (Self::Value(path) | Self::Len(path) | Self::GenArg(path)) = self;
+        let mut value = program_input;
+        for (depth, accessor) in path.iter().enumerate() {
+            let nested = match accessor {
+                Accessor::Key(key) => value.get(key),
+                Accessor::Index(index) => value.get(index),
+            };
+            value =
+                nested.ok_or_else(|| ProgramInputError::MissingKey(path_name(&path[..=depth])))?;
+        }
+
+        let invalid_value = |expected| ProgramInputError::InvalidValue(path_name(path), expected);
+        match self {
+            Self::Value(_) => to_felt(value)
+                .map(InputValue::Felt)
+                .ok_or_else(|| invalid_value("a felt")),
+            Self::Len(_) => value
+                .as_array()
+                .map(|values| InputValue::Felt(Felt252::from(values.len())))
+                .ok_or_else(|| invalid_value("a list")),
+            Self::GenArg(_) => {
+                to_input_value(value).ok_or_else(|| invalid_value("a felt or a list of felts"))
+            }
+        }
+    }
+}
+
+/// Names a value of the program input in error messages, e.g. `config.values.0`.
+fn path_name(path: &[Accessor]) -> String {
+    path.iter()
+        .map(|accessor| match accessor {
+            Accessor::Key(key) => key.to_string(),
+            Accessor::Index(index) => index.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Reads a felt, written as a JSON integer or a string.
+fn to_felt(value: &serde_json::Value) -> Option<Felt252> {
+    match value {
+        serde_json::Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => Some(Felt252::from(value)),
+            (None, Some(value)) => Some(Felt252::from(value)),
+            _ => None,
+        },
+        serde_json::Value::String(value) => parse_felt(value).ok(),
+        _ => None,
+    }
+}
+
+/// Reads a felt or a list, possibly nested, of felts.
+fn to_input_value(value: &serde_json::Value) -> Option<InputValue> {
+    match value {
+        serde_json::Value::Array(values) => values
+            .iter()
+            .map(to_input_value)
+            .collect::<Option<_>>()
+            .map(InputValue::List),
+        value => to_felt(value).map(InputValue::Felt),
+    }
+}
+
+/// Parses `program_input['key'][0]...` into its accessors.
+fn parse_path(expression: &str) -> Option<Vec<Accessor>> {
+    let mut rest = expression.strip_prefix(PROGRAM_INPUT)?;
+    let mut path = vec![];
+    while !rest.is_empty() {
+        let (accessor, next) = rest.strip_prefix('[')?.split_once(']')?;
+        let key = ['\'', '"']
+            .iter()
+            .find_map(|quote| accessor.strip_prefix(*quote)?.strip_suffix(*quote));
+        path.push(match key {
+            Some(key) => Accessor::Key(key),
+            None => Accessor::Index(accessor.parse().ok()?),
+        });
+        rest = next;
+    }
+    Some(path)
+}
+
+fn parse_expression(expression: &str) -> Option<Expression> {
+    let call_argument = |function: &str| {
+        expression
+            .strip_prefix(function)?
+            .strip_prefix('(')?
+            .strip_suffix(')')
+            .map(str::trim)
+    };
+    if let Some(argument) = call_argument("segments.gen_arg") {
+        return parse_path(argument).map(Expression::GenArg);
+    }
+    if let Some(argument) = call_argument("len") {
+        return parse_path(argument).map(Expression::Len);
+    }
+    parse_path(expression).map(Expression::Value)
+}
+
+/// Parses the lines of a hint of the form `ids.<name> = <expression>`, where the expression
+/// reads the program input, into `(name, expression)` pairs.
+fn parse_assignments(code: &str) -> Result<Vec<(&str, Expression)>, ProgramInputError> {
+    let unsupported = || ProgramInputError::UnsupportedHint(code.to_string());
+    let is_identifier = |name: &str| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+
+    code.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (name, expression) = line.split_once('=').ok_or_else(unsupported)?;
+            let name = name.trim().strip_prefix("ids.").ok_or_else(unsupported)?;
+            let expression = parse_expression(expression.trim()).ok_or_else(unsupported)?;
+            if !is_identifier(name) {
+                return Err(unsupported());
+            }
+            Ok((name, expression))
+        })
+        .collect()
+}
+
+/// Writes a value to memory, lists to a new segment, and returns what the variable holds:
+/// the felt or a pointer to the list. As in cairo-lang, the segment of a list is added
+/// before the segments of its nested lists.
+fn write_value(vm: &mut VirtualMachine, value: &InputValue) -> Result<MaybeRelocatable, HintError> {
+    match value {
+        InputValue::Felt(felt) => Ok(MaybeRelocatable::from(*felt)),
+        InputValue::List(values) => {
+            let base = vm.add_memory_segment();
+            let data = values
+                .iter()
+                .map(|value| write_value(vm, value))
+                .collect::<Result<Vec<_>, _>>()?;
+            vm.load_data(base, &data).map_err(HintError::Memory)?;
+            Ok(MaybeRelocatable::from(base))
+        }
+    }
+}
+
+fn assignment_hint(assignments: Vec<(String, InputValue)>) -> Rc<HintFunc> {
+    let hint = move |vm: &mut VirtualMachine,
+                     _exec_scopes: &mut ExecutionScopes,
+                     ids_data: &HashMap<String, HintReference>,
+                     ap_tracking: &ApTracking,
+                     _constants: &HashMap<String, Felt252>|
+          -> Result<(), HintError> {
+        for (name, value) in &assignments {
+            let value = write_value(vm, value)?;
+            insert_value_from_var_name(name, value, vm, ids_data, ap_tracking)?;
+        }
+        Ok(())
+    };
+    Rc::new(HintFunc(Box::new(hint)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("ids.n = program_input['n']", vec![("n", Expression::Value(vec![Accessor::Key("n")]))])]
+    #[case(
+        "ids.a = program_input[\"x\"]\nids.b_1 = program_input['y']\n",
+        vec![
+            ("a", Expression::Value(vec![Accessor::Key("x")])),
+            ("b_1", Expression::Value(vec![Accessor::Key("y")])),
+        ]
+    )]
+    #[case(
+        "ids.values = segments.gen_arg(program_input['config']['values'])\nids.n = len(program_input['config']['values'])",
+        vec![
+            ("values", Expression::GenArg(vec![Accessor::Key("config"), Accessor::Key("values")])),
+            ("n", Expression::Len(vec![Accessor::Key("config"), Accessor::Key("values")])),
+        ]
+    )]
+    #[case(
+        "ids.x = program_input['points'][1]['x']",
+        vec![("x", Expression::Value(vec![Accessor::Key("points"), Accessor::Index(1), Accessor::Key("x")]))]
+    )]
+    fn test_parse_assignments(#[case] code: &str, #[case] expected: Vec<(&str, Expression)>) {
+        assert_eq!(parse_assignments(code).unwrap(), expected);
+    }
+
+    #[rstest]
+    #[case("ids.n = sum(program_input['values'])")]
+    #[case("memory[ap] = program_input['n']")]
+    #[case("ids.n.x = program_input['n']")]
+    #[case("ids.n == program_input['n']")]
+    #[case("ids.n = program_input['values'][-1]")]
+    #[case("ids.n = program_input['n'] + 1")]
+    fn test_parse_unsupported_assignments(#[case] code: &str) {
+        assert_eq!(
+            parse_assignments(code).unwrap_err(),
+            ProgramInputError::UnsupportedHint(code.to_string())
+        );
+    }
+
+    fn resolve(
+        code: &str,
+        program_input: serde_json::Value,
+    ) -> Result<InputValue, ProgramInputError> {
+        let (_, expression) = parse_assignments(code).unwrap().remove(0);
+        expression.resolve(&program_input)
+    }
+
+    #[rstest]
+    #[case(serde_json::json!({"n": 10}), Ok(InputValue::Felt(Felt252::from(10))))]
+    #[case(serde_json::json!({"n": -1}), Ok(InputValue::Felt(Felt252::from(-1))))]
+    #[case(serde_json::json!({"n": "0x10"}), Ok(InputValue::Felt(Felt252::from(16))))]
+    #[case(serde_json::json!({"n": [1]}), Err(ProgramInputError::InvalidValue("n".to_string(), "a felt")))]
+    #[case(serde_json::json!({}), Err(ProgramInputError::MissingKey("n".to_string())))]
+    fn test_resolve_value(
+        #[case] program_input: serde_json::Value,
+        #[case] expected: Result<InputValue, ProgramInputError>,
+    ) {
+        assert_eq!(
+            resolve("ids.n = program_input['n']", program_input),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_resolve_nested_values() {
+        let program_input = serde_json::json!({"config": {"values": [5, "0x7", [1, 2]]}});
+        let felt = |value: u64| InputValue::Felt(Felt252::from(value));
+
+        assert_eq!(
+            resolve(
+                "ids.v = segments.gen_arg(program_input['config']['values'])",
+                program_input.clone()
+            ),
+            Ok(InputValue::List(vec![
+                felt(5),
+                felt(7),
+                InputValue::List(vec![felt(1), felt(2)]),
+            ]))
+        );
+        assert_eq!(
+            resolve(
+                "ids.n = len(program_input['config']['values'])",
+                program_input.clone()
+            ),
+            Ok(felt(3))
+        );
+        assert_eq!(
+            resolve(
+                "ids.n = program_input['config']['values'][2][1]",
+                program_input.clone()
+            ),
+            Ok(felt(2))
+        );
+        assert_eq!(
+            resolve(
+                "ids.n = program_input['config']['values'][3]",
+                program_input.clone()
+            ),
+            Err(ProgramInputError::MissingKey("config.values.3".to_string()))
+        );
+        assert_eq!(
+            resolve(
+                "ids.n = len(program_input['config'])",
+                program_input.clone()
+            ),
+            Err(ProgramInputError::InvalidValue(
+                "config".to_string(),
+                "a list"
+            ))
+        );
+        assert_eq!(
+            resolve(
+                "ids.v = segments.gen_arg(program_input['config'])",
+                program_input
+            ),
+            Err(ProgramInputError::InvalidValue(
+                "config".to_string(),
+                "a felt or a list of felts"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_loaded_program_input_hints() {
+        let program = serde_json::json!({
+            "hints": {
+                "2": [{"code": "ids.n = program_input['n']"}],
+                "5": [{"code": "memory[ap] = 1"}],
+            }
+        });
+        let program_input = LoadedProgramInput::new(serde_json::json!({"n": 3}), &program).unwrap();
+        assert_eq!(
+            program_input.hints.keys().collect::<Vec<_>>(),
+            vec!["ids.n = program_input['n']"]
+        );
+
+        let result = LoadedProgramInput::new(serde_json::json!({"m": 3}), &program);
+        assert!(matches!(result, Err(ProgramInputError::MissingKey(key)) if key == "n"));
+    }
+}
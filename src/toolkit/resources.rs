@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use stone_prover_sdk::cairo_vm::ExecutionArtifacts;
+use stone_prover_sdk::models::{Layout, PublicInput};
+
+use crate::toolkit::layout::{layout_builtins, BuiltinSpec};
+
+/// Size of a cell in the binary memory file: an 8-byte address and a 32-byte value.
+pub const MEMORY_CELL_SIZE: usize = 40;
+
+/// Size of an entry in the binary trace file: the ap, fp and pc registers as 8-byte values.
+pub const TRACE_ENTRY_SIZE: usize = 24;
+
+/// Segments of the public input that do not belong to a builtin.
+const NON_BUILTIN_SEGMENTS: [&str; 2] = ["program", "execution"];
+
+pub(crate) fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().unwrap())
+}
+
+/// The registers of a step, read from an entry of the binary trace file.
+pub(crate) struct TraceEntry {
+    pub(crate) ap: u64,
+    pub(crate) fp: u64,
+    pub(crate) pc: u64,
+}
+
+pub(crate) fn read_trace(trace: &[u8]) -> Vec<TraceEntry> {
+    trace
+        .chunks_exact(TRACE_ENTRY_SIZE)
+        .map(|entry| TraceEntry {
+            ap: read_u64(entry),
+            fp: read_u64(&entry[8..]),
+            pc: read_u64(&entry[16..]),
+        })
+        .collect()
+}
+
+/// Counts the padding steps at the end of the trace. Once the program reaches `__end__`,
+/// proof mode runs `jmp rel 0` until the trace length is valid, so the padding steps are
+/// the trailing entries sharing the pc of the last one.
+pub fn count_padding_steps(trace: &[u8]) -> usize {
+    let entries = read_trace(trace);
+    let Some(last) = entries.last() else {
+        return 0;
+    };
+    entries
+        .iter()
+        .rev()
+        .take_while(|entry| entry.pc == last.pc)
+        .count()
+}
+
+/// Returns the number of memory cells and the number of unused addresses below the highest
+/// one, i.e. the memory holes.
+pub fn count_memory_cells(memory: &[u8]) -> (usize, usize) {
+    let addresses = memory.chunks_exact(MEMORY_CELL_SIZE).map(read_u64);
+    let n_cells = memory.len() / MEMORY_CELL_SIZE;
+    // Relocated addresses start at 1
+    let max_address = addresses.max().unwrap_or(0) as usize;
+    (n_cells, max_address.saturating_sub(n_cells))
+}
+
+/// Returns the number of cells available to a builtin for a trace length, or `None` if
+/// the builtin does not depend on the trace length.
+pub fn builtin_capacity(spec: &BuiltinSpec, trace_length: usize) -> Option<usize> {
+    let ratio = spec.ratio? as usize;
+    let n_components = trace_length / ratio;
+    Some(n_components * (spec.instances_per_component * spec.cells_per_instance) as usize)
+}
+
+/// Returns the number of cells used by each builtin, from the memory segments of the
+/// public input.
+pub fn builtin_cells(public_input: &PublicInput) -> impl Iterator<Item = (&str, usize)> {
+    public_input
+        .memory_segments
+        .iter()
+        .filter(|(name, _)| !NON_BUILTIN_SEGMENTS.contains(&name.as_str()))
+        .map(|(name, segment)| {
+            let used_cells = segment.stop_ptr.saturating_sub(segment.begin_addr) as usize;
+            (name.as_str(), used_cells)
+        })
+}
+
+/// Returns the number of cells available to a builtin in the layout of the public input.
+fn layout_capacity(public_input: &PublicInput, name: &str) -> Option<usize> {
+    let spec = layout_builtins(public_input.layout)?
+        .iter()
+        .find(|spec| spec.name == name)?;
+    builtin_capacity(spec, public_input.n_steps as usize)
+}
+
+#[derive(Serialize, Debug)]
+pub struct BuiltinUsage {
+    pub used_cells: usize,
+    /// `None` if the capacity does not depend on the trace length or is unknown for
+    /// the layout.
+    pub capacity_cells: Option<usize>,
+}
+
+/// Resources used by an execution, compared to what its layout allows.
+#[derive(Serialize, Debug)]
+pub struct ExecutionReport<'a> {
+    pub layout: &'a Layout,
+    pub n_steps: usize,
+    pub trace_length: usize,
+    pub padding_steps: usize,
+    pub n_memory_cells: usize,
+    pub n_memory_holes: usize,
+    pub builtins: BTreeMap<&'a str, BuiltinUsage>,
+}
+
+impl<'a> ExecutionReport<'a> {
+    pub fn new(execution_artifacts: &'a ExecutionArtifacts) -> Self {
+        let public_input = &execution_artifacts.public_input;
+        let trace_length = public_input.n_steps as usize;
+        let padding_steps = count_padding_steps(&execution_artifacts.trace);
+        let (n_memory_cells, n_memory_holes) = count_memory_cells(&execution_artifacts.memory);
+
+        let builtins = builtin_cells(public_input)
+            .map(|(name, used_cells)| {
+                let usage = BuiltinUsage {
+                    used_cells,
+                    capacity_cells: layout_capacity(public_input, name),
+                };
+                (name, usage)
+            })
+            .collect();
+
+        Self {
+            layout: &public_input.layout,
+            n_steps: trace_length.saturating_sub(padding_steps),
+            trace_length,
+            padding_steps,
+            n_memory_cells,
+            n_memory_holes,
+            builtins,
+        }
+    }
+
+    pub fn print(&self) {
+        let percentage = |part: usize, total: usize| match total {
+            0 => 0.0,
+            _ => 100.0 * part as f64 / total as f64,
+        };
+
+        println!("Layout:       {}", self.layout);
+        println!("Steps:        {}", self.n_steps);
+        println!(
+            "Trace length: {} ({:.1}% padding)",
+            self.trace_length,
+            percentage(self.padding_steps, self.trace_length)
+        );
+        println!(
+            "Memory cells: {} ({} holes)",
+            self.n_memory_cells, self.n_memory_holes
+        );
+        println!();
+
+        println!("Builtins:");
+        for (name, usage) in &self.builtins {
+            match usage.capacity_cells {
+                Some(capacity) => println!(
+                    "  {name:<12} {} / {capacity} cells ({:.1}%)",
+                    usage.used_cells,
+                    percentage(usage.used_cells, capacity)
+                ),
+                None => println!("  {name:<12} {} cells", usage.used_cells),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trace(pcs: &[u64]) -> Vec<u8> {
+        pcs.iter()
+            .flat_map(|pc| [100u64, 100, *pc])
+            .flat_map(u64::to_le_bytes)
+            .collect()
+    }
+
+    fn memory(addresses: &[u64]) -> Vec<u8> {
+        addresses
+            .iter()
+            .flat_map(|address| {
+                let mut cell = address.to_le_bytes().to_vec();
+                cell.extend([0u8; 32]);
+                cell
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_count_padding_steps() {
+        assert_eq!(count_padding_steps(&trace(&[1, 2, 3, 9, 9, 9, 9, 9])), 5);
+        assert_eq!(count_padding_steps(&trace(&[])), 0);
+    }
+
+    #[test]
+    fn test_count_memory_cells() {
+        assert_eq!(count_memory_cells(&memory(&[1, 2, 3, 6, 7])), (5, 2));
+        assert_eq!(count_memory_cells(&memory(&[])), (0, 0));
+    }
+
+    #[test]
+    fn test_builtin_capacity() {
+        let specs = layout_builtins(Layout::StarknetWithKeccak).unwrap();
+        let capacity = |name: &str| {
+            let spec = specs.iter().find(|spec| spec.name == name).unwrap();
+            builtin_capacity(spec, 65536)
+        };
+
+        // 65536 / 32 instances of 3 cells
+        assert_eq!(capacity("pedersen"), Some(6144));
+        // 65536 / 2048 components of 16 instances of 16 cells
+        assert_eq!(capacity("keccak"), Some(8192));
+        assert_eq!(capacity("output"), None);
+    }
+}
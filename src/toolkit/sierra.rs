@@ -0,0 +1,200 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use cairo1_run::{cairo_run_program, Cairo1RunConfig, FuncArg};
+use cairo_lang_sierra::program::{Program as SierraProgram, VersionedProgram};
+use cairo_vm::vm::runners::cairo_pie::CairoPie;
+use cairo_vm::vm::runners::cairo_runner::CairoRunner;
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+use serde::Deserialize;
+use stone_prover_sdk::models::Layout;
+
+use crate::toolkit::felt::{parse_felt, ParseFeltError};
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum FunctionArgsError {
+    #[error(transparent)]
+    InvalidFelt(#[from] ParseFeltError),
+
+    #[error("nested arrays are not supported")]
+    NestedArray,
+
+    #[error("unbalanced brackets")]
+    UnbalancedBrackets,
+}
+
+/// An argument of the `main` function of a Cairo 1 program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FunctionArg {
+    Single(Felt252),
+    Array(Vec<Felt252>),
+}
+
+impl From<&FunctionArg> for FuncArg {
+    fn from(arg: &FunctionArg) -> Self {
+        match arg {
+            FunctionArg::Single(value) => FuncArg::Single(*value),
+            FunctionArg::Array(values) => FuncArg::Array(values.clone()),
+        }
+    }
+}
+
+/// The arguments of the `main` function of a Cairo 1 program, parsed from a list of felts
+/// where arrays are enclosed in brackets, e.g. `1 [2 3 4] 0x5`.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "String")]
+pub struct FunctionArgs(pub Vec<FunctionArg>);
+
+impl FromStr for FunctionArgs {
+    type Err = FunctionArgsError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value
+            .replace('[', " [ ")
+            .replace(']', " ] ")
+            .replace(',', " ");
+
+        let mut args = vec![];
+        let mut array: Option<Vec<Felt252>> = None;
+        for token in value.split_whitespace() {
+            match token {
+                "[" if array.is_some() => return Err(FunctionArgsError::NestedArray),
+                "[" => array = Some(vec![]),
+                "]" => match array.take() {
+                    Some(values) => args.push(FunctionArg::Array(values)),
+                    None => return Err(FunctionArgsError::UnbalancedBrackets),
+                },
+                token => {
+                    let felt = parse_felt(token)?;
+                    match array.as_mut() {
+                        Some(values) => values.push(felt),
+                        None => args.push(FunctionArg::Single(felt)),
+                    }
+                }
+            }
+        }
+        if array.is_some() {
+            return Err(FunctionArgsError::UnbalancedBrackets);
+        }
+
+        Ok(Self(args))
+    }
+}
+
+impl TryFrom<String> for FunctionArgs {
+    type Error = FunctionArgsError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SierraError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid Sierra program: {0}")]
+    Deserialize(#[from] serde_json::Error),
+
+    #[error("Starknet contract classes cannot be run, compile the program with `main` as executable instead")]
+    ContractClass,
+
+    #[error("unsupported Sierra program: {0}")]
+    UnsupportedVersion(String),
+
+    #[error("{0}")]
+    RunFailed(String),
+}
+
+/// Loads a Sierra program, as written by `cairo-compile` or Scarb.
+pub fn load_sierra_program(path: &Path) -> Result<SierraProgram, SierraError> {
+    let content = std::fs::read_to_string(path)?;
+    let value: serde_json::Value = serde_json::from_str(&content)?;
+    if value.get("sierra_program").is_some() {
+        return Err(SierraError::ContractClass);
+    }
+
+    let versioned_program: VersionedProgram = serde_json::from_value(value)?;
+    let program_artifact = versioned_program
+        .into_v1()
+        .map_err(|e| SierraError::UnsupportedVersion(e.to_string()))?;
+
+    Ok(program_artifact.program)
+}
+
+/// Runs the `main` function of a Sierra program. The return values of the function are
+/// written to the output segment, like the output of a Cairo 0 program.
+pub fn run_sierra_program(
+    program: &SierraProgram,
+    args: Option<&FunctionArgs>,
+    layout: Layout,
+    proof_mode: bool,
+) -> Result<(CairoRunner, VirtualMachine), SierraError> {
+    let args: Vec<FuncArg> = args
+        .map(|args| args.0.iter().map(FuncArg::from).collect())
+        .unwrap_or_default();
+    let layout_str = layout.to_string();
+    let cairo_run_config = Cairo1RunConfig {
+        args: &args,
+        trace_enabled: proof_mode,
+        relocate_mem: proof_mode,
+        layout: &layout_str,
+        proof_mode,
+        // Required to extract the public input or a Cairo PIE
+        finalize_builtins: true,
+        append_return_values: true,
+        ..Default::default()
+    };
+
+    let (runner, vm, ..) = cairo_run_program(program, cairo_run_config)
+        .map_err(|e| SierraError::RunFailed(e.to_string()))?;
+    Ok((runner, vm))
+}
+
+/// Runs a Sierra program outside of proof mode and returns the execution as a Cairo PIE.
+pub fn sierra_to_cairo_pie(
+    program: &SierraProgram,
+    args: Option<&FunctionArgs>,
+    layout: Layout,
+) -> Result<CairoPie, SierraError> {
+    let (runner, vm) = run_sierra_program(program, args, layout, false)?;
+    runner
+        .get_cairo_pie(&vm)
+        .map_err(|e| SierraError::RunFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("", vec![])]
+    #[case("1 0x2", vec![FunctionArg::Single(Felt252::from(1)), FunctionArg::Single(Felt252::from(2))])]
+    #[case(
+        "1 [2 3] []",
+        vec![
+            FunctionArg::Single(Felt252::from(1)),
+            FunctionArg::Array(vec![Felt252::from(2), Felt252::from(3)]),
+            FunctionArg::Array(vec![]),
+        ]
+    )]
+    #[case("[1, 2],3", vec![FunctionArg::Array(vec![Felt252::from(1), Felt252::from(2)]), FunctionArg::Single(Felt252::from(3))])]
+    fn test_parse_function_args(#[case] value: &str, #[case] expected: Vec<FunctionArg>) {
+        assert_eq!(
+            value.parse::<FunctionArgs>().unwrap(),
+            FunctionArgs(expected)
+        );
+    }
+
+    #[rstest]
+    #[case("[1 [2]]", FunctionArgsError::NestedArray)]
+    #[case("[1 2", FunctionArgsError::UnbalancedBrackets)]
+    #[case("1 2]", FunctionArgsError::UnbalancedBrackets)]
+    #[case("1 abc", FunctionArgsError::InvalidFelt(ParseFeltError::InvalidFelt("abc".to_string())))]
+    fn test_parse_invalid_function_args(#[case] value: &str, #[case] expected: FunctionArgsError) {
+        assert_eq!(value.parse::<FunctionArgs>().unwrap_err(), expected);
+    }
+}
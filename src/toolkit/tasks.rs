@@ -0,0 +1,293 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::toolkit::sierra::FunctionArgs;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TaskManifestError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+
+    #[error("task {0} uses Poseidon program hashes, the bootloader only supports Pedersen")]
+    PoseidonNotSupported(String),
+}
+
+/// The program of a bootloader task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskProgram {
+    /// Compiled program or Cairo PIE file.
+    Path(PathBuf),
+    /// Compiled program embedded in a bootloader input file. `origin` identifies the task
+    /// in error messages.
+    Inline {
+        program: serde_json::Value,
+        origin: PathBuf,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgramInput {
+    Path(PathBuf),
+    Inline(serde_json::Value),
+}
+
+/// A task to run with the bootloader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootloaderTask {
+    pub program: TaskProgram,
+    /// Made available to the hints of the program as `program_input`.
+    pub program_input: Option<ProgramInput>,
+    /// Arguments of the `main` function of Sierra programs.
+    pub args: Option<FunctionArgs>,
+    /// Function called by the bootloader to run the program. Ignored for Cairo PIEs.
+    pub entrypoint: Option<String>,
+}
+
+impl BootloaderTask {
+    pub fn from_path(path: PathBuf) -> Self {
+        Self {
+            program: TaskProgram::Path(path),
+            program_input: None,
+            args: None,
+            entrypoint: None,
+        }
+    }
+
+    /// Identifies the task in error messages.
+    pub fn name(&self) -> &Path {
+        match &self.program {
+            TaskProgram::Path(path) => path,
+            TaskProgram::Inline { origin, .. } => origin,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ManifestTask {
+    path: PathBuf,
+    program_input: Option<PathBuf>,
+    args: Option<FunctionArgs>,
+    entrypoint: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct TaskManifest {
+    tasks: Vec<ManifestTask>,
+}
+
+/// Reads a task manifest, in TOML format if the file has a `.toml` extension and in JSON
+/// format otherwise. Relative paths are resolved from the directory of the manifest.
+pub fn read_task_manifest(path: &Path) -> Result<Vec<BootloaderTask>, TaskManifestError> {
+    let content = std::fs::read_to_string(path)?;
+    let manifest: TaskManifest = match path.extension() {
+        Some(extension) if extension == "toml" => toml::from_str(&content)?,
+        _ => serde_json::from_str(&content)?,
+    };
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    manifest
+        .tasks
+        .into_iter()
+        .map(|task| BootloaderTask {
+            program: TaskProgram::Path(base_dir.join(task.path)),
+            program_input: task
+                .program_input
+                .map(|input| ProgramInput::Path(base_dir.join(input))),
+            args: task.args,
+            entrypoint: task.entrypoint,
+        })
+        .collect()
+}
+
+/// A task of the cairo-lang simple bootloader input.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "type")]
+enum SimpleBootloaderTask {
+    RunProgramTask {
+        program: serde_json::Value,
+        program_input: Option<serde_json::Value>,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+    CairoPiePath {
+        path: PathBuf,
+        #[serde(default)]
+        use_poseidon: bool,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+struct SimpleBootloaderInput {
+    tasks: Vec<SimpleBootloaderTask>,
+    fact_topologies_path: Option<PathBuf>,
+    #[serde(default)]
+    single_page: bool,
+}
+
+/// The content of a simple bootloader input file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootloaderInput {
+    pub tasks: Vec<BootloaderTask>,
+    pub fact_topologies_path: Option<PathBuf>,
+    pub single_page: bool,
+}
+
+/// Reads a bootloader input file following the cairo-lang simple bootloader input schema.
+///
+/// Like in cairo-lang, paths are used as is, i.e. relative paths are resolved from the
+/// current directory.
+pub fn read_bootloader_input(path: &Path) -> Result<BootloaderInput, TaskManifestError> {
+    let content = std::fs::read_to_string(path)?;
+    let input: SimpleBootloaderInput = serde_json::from_str(&content)?;
+
+    let tasks = input
+        .tasks
+        .into_iter()
+        .enumerate()
+        .map(|(index, task)| match task {
+            SimpleBootloaderTask::RunProgramTask { use_poseidon, .. }
+            | SimpleBootloaderTask::CairoPiePath { use_poseidon, .. }
+                if use_poseidon =>
+            {
+                Err(TaskManifestError::PoseidonNotSupported(format!(
+                    "{}#{index}",
+                    path.to_string_lossy()
+                )))
+            }
+            SimpleBootloaderTask::RunProgramTask {
+                program,
+                program_input,
+                ..
+            } => Ok(BootloaderTask {
+                program: TaskProgram::Inline {
+                    program,
+                    origin: PathBuf::from(format!("{}#{index}", path.to_string_lossy())),
+                },
+                program_input: program_input.map(ProgramInput::Inline),
+                args: None,
+                entrypoint: None,
+            }),
+            SimpleBootloaderTask::CairoPiePath { path, .. } => Ok(BootloaderTask {
+                program: TaskProgram::Path(path),
+                program_input: None,
+                args: None,
+                entrypoint: None,
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(BootloaderInput {
+        tasks,
+        fact_topologies_path: input.fact_topologies_path,
+        single_page: input.single_page,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "tasks.toml",
+        r#"
+        [[tasks]]
+        path = "program.json"
+        program_input = "input.json"
+        entrypoint = "run"
+
+        [[tasks]]
+        path = "/pies/cairo_pie.zip"
+        "#
+    )]
+    #[case(
+        "tasks.json",
+        r#"{"tasks": [
+            {"path": "program.json", "program_input": "input.json", "entrypoint": "run"},
+            {"path": "/pies/cairo_pie.zip"}
+        ]}"#
+    )]
+    fn test_read_task_manifest(#[case] file_name: &str, #[case] content: &str) {
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest_file = manifest_dir.path().join(file_name);
+        std::fs::write(&manifest_file, content).unwrap();
+
+        let tasks = read_task_manifest(&manifest_file).unwrap();
+        assert_eq!(
+            tasks,
+            vec![
+                BootloaderTask {
+                    program: TaskProgram::Path(manifest_dir.path().join("program.json")),
+                    program_input: Some(ProgramInput::Path(manifest_dir.path().join("input.json"))),
+                    args: None,
+                    entrypoint: Some("run".to_string()),
+                },
+                BootloaderTask {
+                    program: TaskProgram::Path(PathBuf::from("/pies/cairo_pie.zip")),
+                    program_input: None,
+                    args: None,
+                    entrypoint: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_bootloader_input() {
+        let input_dir = tempfile::tempdir().unwrap();
+        let input_file = input_dir.path().join("bootloader_input.json");
+        std::fs::write(
+            &input_file,
+            r#"{
+                "tasks": [
+                    {"type": "RunProgramTask", "program": {"data": []}, "program_input": {"n": 10}},
+                    {"type": "CairoPiePath", "path": "cairo_pie.zip", "use_poseidon": false}
+                ],
+                "single_page": true
+            }"#,
+        )
+        .unwrap();
+
+        let bootloader_input = read_bootloader_input(&input_file).unwrap();
+        assert_eq!(bootloader_input.tasks.len(), 2);
+        assert!(matches!(
+            &bootloader_input.tasks[0].program_input,
+            Some(ProgramInput::Inline(value)) if value["n"] == 10
+        ));
+        assert_eq!(
+            bootloader_input.tasks[1],
+            BootloaderTask {
+                program: TaskProgram::Path(PathBuf::from("cairo_pie.zip")),
+                program_input: None,
+                args: None,
+                entrypoint: None,
+            }
+        );
+        assert_eq!(bootloader_input.fact_topologies_path, None);
+        assert!(bootloader_input.single_page);
+    }
+
+    #[rstest]
+    #[case(r#"{"type": "RunProgramTask", "program": {"data": []}, "use_poseidon": true}"#)]
+    #[case(r#"{"type": "CairoPiePath", "path": "cairo_pie.zip", "use_poseidon": true}"#)]
+    fn test_read_bootloader_input_rejects_poseidon(#[case] task: &str) {
+        let input_dir = tempfile::tempdir().unwrap();
+        let input_file = input_dir.path().join("bootloader_input.json");
+        std::fs::write(&input_file, format!(r#"{{"tasks": [{task}]}}"#)).unwrap();
+
+        assert!(matches!(
+            read_bootloader_input(&input_file),
+            Err(TaskManifestError::PoseidonNotSupported(task)) if task.ends_with("bootloader_input.json#0")
+        ));
+    }
+}
@@ -0,0 +1,17 @@
+%builtins output
+
+// Writes 1 to the output.
+func main(output_ptr: felt*) -> (output_ptr: felt*) {
+    alloc_locals;
+    local n = 1;
+    assert [output_ptr] = n;
+    return (output_ptr=output_ptr + 1);
+}
+
+// Writes 2 to the output, run with `--entrypoint write_two`.
+func write_two(output_ptr: felt*) -> (output_ptr: felt*) {
+    alloc_locals;
+    local n = 2;
+    assert [output_ptr] = n;
+    return (output_ptr=output_ptr + 1);
+}
@@ -0,0 +1,10 @@
+%builtins output
+
+// Writes the `n` value of the program input to the output.
+func main(output_ptr: felt*) -> (output_ptr: felt*) {
+    alloc_locals;
+    local n;
+    %{ ids.n = program_input['n'] %}
+    assert [output_ptr] = n;
+    return (output_ptr=output_ptr + 1);
+}
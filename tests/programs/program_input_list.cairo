@@ -0,0 +1,16 @@
+%builtins output
+
+// Writes the number of values in `config.values` of the program input, then the second
+// value, to the output.
+func main(output_ptr: felt*) -> (output_ptr: felt*) {
+    alloc_locals;
+    local values: felt*;
+    local n;
+    %{
+        ids.values = segments.gen_arg(program_input['config']['values'])
+        ids.n = len(program_input['config']['values'])
+    %}
+    assert [output_ptr] = n;
+    assert [output_ptr + 1] = values[1];
+    return (output_ptr=output_ptr + 2);
+}
@@ -0,0 +1,4 @@
+// Returns the sum of its arguments, written to the output when run with `--args "a b"`.
+fn main(a: felt252, b: felt252) -> felt252 {
+    a + b
+}
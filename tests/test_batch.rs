@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::Proof;
+
+use crate::common::cli_in_path;
+
+mod common;
+
+#[rstest]
+fn prove_batch(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let manifest_file = output_dir.path().join("jobs.toml");
+    let report_file = output_dir.path().join("report.json");
+
+    let test_case_dir =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("dependencies/cairo-programs/cairo0/fibonacci");
+    let program = test_case_dir.join("fibonacci.json");
+    let expected_proof = test_case_dir.join("proof.json");
+
+    let manifest = format!(
+        r#"
+        [[jobs]]
+        name = "fibonacci"
+        programs = ["{}"]
+        layout = "starknet_with_keccak"
+
+        [[jobs]]
+        name = "missing"
+        programs = ["missing.json"]
+        "#,
+        program.to_string_lossy()
+    );
+    std::fs::write(&manifest_file, manifest).unwrap();
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("batch")
+        .arg(&manifest_file)
+        .arg("--concurrency")
+        .arg("2")
+        .arg("--report-file")
+        .arg(&report_file)
+        .output()
+        .expect("Command should succeed");
+
+    // One of the jobs fails, the other one must still be proved
+    assert!(!result.status.success());
+
+    let proof: Proof = read_json_from_file(output_dir.path().join("fibonacci_proof.json")).unwrap();
+    let expected_proof: Proof = read_json_from_file(expected_proof).unwrap();
+    assert_eq!(proof.proof_hex, expected_proof.proof_hex);
+
+    let report: serde_json::Value = read_json_from_file(report_file).unwrap();
+    assert_eq!(report["n_succeeded"], 1);
+    assert_eq!(report["n_failed"], 1);
+    assert_eq!(report["jobs"][0]["status"], "succeeded");
+    assert_eq!(report["jobs"][1]["status"], "failed");
+}
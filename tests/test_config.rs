@@ -0,0 +1,42 @@
+use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::{ProverConfig, ProverParameters};
+
+use crate::common::cli_in_path;
+
+mod common;
+
+#[rstest]
+fn init_config(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let invoke_cli = || {
+        std::process::Command::new("stone-prover-cli")
+            .arg("config")
+            .arg("init")
+            .arg("--n-steps")
+            .arg("16384")
+            .arg("--output-dir")
+            .arg(output_dir.path())
+            .output()
+            .expect("Command should succeed")
+    };
+
+    let result = invoke_cli();
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(String::from_utf8_lossy(&result.stdout).contains("n_queries"));
+
+    let prover_config: ProverConfig =
+        read_json_from_file(output_dir.path().join("cpu_air_prover_config.json")).unwrap();
+    assert_eq!(prover_config, ProverConfig::default());
+    let _parameters: ProverParameters =
+        read_json_from_file(output_dir.path().join("cpu_air_params.json")).unwrap();
+
+    // Existing files are not overwritten without --force
+    let result = invoke_cli();
+    assert!(!result.status.success());
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use rstest::rstest;
+
+use crate::common::cli_in_path;
+
+mod common;
+
+fn invoke_cli(args: &[&str]) -> Result<std::process::Output, std::io::Error> {
+    let mut command = std::process::Command::new("stone-prover-cli");
+    command.arg("fact").args(args).arg("--format").arg("json");
+
+    command.output()
+}
+
+fn parse_facts(output: std::process::Output) -> Vec<serde_json::Value> {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[rstest]
+fn compute_program_fact(
+    #[from(cli_in_path)] _path: (),
+    #[values("pedersen", "poseidon")] hash_function: &str,
+) {
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = invoke_cli(&[
+        "--program",
+        program.to_str().unwrap(),
+        "--output",
+        "[10, 144]",
+        "--hash-function",
+        hash_function,
+    ])
+    .expect("Command should succeed");
+    let facts = parse_facts(result);
+
+    assert_eq!(facts.len(), 1);
+    assert_eq!(facts[0]["output_size"], 2);
+    assert_eq!(facts[0]["fact"].as_str().unwrap().len(), 66);
+}
+
+#[rstest]
+fn compute_program_fact_from_proof(#[from(cli_in_path)] _path: ()) {
+    let test_case_dir =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("dependencies/cairo-programs/cairo0/fibonacci");
+    let program = test_case_dir.join("fibonacci.json");
+    let proof = test_case_dir.join("proof.json");
+
+    let result = invoke_cli(&[
+        "--program",
+        program.to_str().unwrap(),
+        "--proof",
+        proof.to_str().unwrap(),
+    ])
+    .expect("Command should succeed");
+    let facts = parse_facts(result);
+
+    assert_eq!(facts.len(), 1);
+}
+
+#[rstest]
+fn compute_bootloader_facts(#[from(cli_in_path)] _path: ()) {
+    let proof = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/pies/fibonacci-stone-e2e/output/proof.json");
+
+    let result = invoke_cli(&["--with-bootloader", "--proof", proof.to_str().unwrap()])
+        .expect("Command should succeed");
+    let facts = parse_facts(result);
+
+    assert_eq!(facts.len(), 1);
+}
+
+/// The bootloader computes the hash of each task in Cairo, which makes it a reference for
+/// the program hash and fact computed from the compiled program.
+#[rstest]
+fn program_fact_matches_bootloader_fact(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let proof_file = output_dir.path().join("proof.json");
+    let output_values_file = output_dir.path().join("output.json");
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/programs/fibonacci/program.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg("--with-bootloader")
+        .arg(&program)
+        .arg("--output-file")
+        .arg(&proof_file)
+        .arg("--output-values-file")
+        .arg(&output_values_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let result = invoke_cli(&["--with-bootloader", "--proof", proof_file.to_str().unwrap()])
+        .expect("Command should succeed");
+    let bootloader_facts = parse_facts(result);
+    assert_eq!(bootloader_facts.len(), 1);
+
+    let output_values: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&output_values_file).unwrap()).unwrap();
+    let task_output = output_values[0]["output"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| value.as_str().unwrap())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let result = invoke_cli(&[
+        "--program",
+        program.to_str().unwrap(),
+        "--output",
+        &format!("[{task_output}]"),
+    ])
+    .expect("Command should succeed");
+    let program_facts = parse_facts(result);
+
+    assert_eq!(
+        program_facts[0]["program_hash"],
+        bootloader_facts[0]["program_hash"]
+    );
+    assert_eq!(program_facts[0]["fact"], bootloader_facts[0]["fact"]);
+}
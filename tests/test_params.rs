@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::ProverParameters;
+
+use crate::common::cli_in_path;
+
+mod common;
+
+fn invoke_cli(args: &[&str], output_file: &Path) -> Result<std::process::Output, std::io::Error> {
+    let mut command = std::process::Command::new("stone-prover-cli");
+    command
+        .arg("params")
+        .arg("generate")
+        .args(args)
+        .arg("--output-file")
+        .arg(output_file);
+
+    command.output()
+}
+
+#[rstest]
+fn generate_params_from_program(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let parameter_file = output_dir.path().join("cpu_air_params.json");
+
+    let test_case_dir =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("dependencies/cairo-programs/cairo0/fibonacci");
+    let program = test_case_dir.join("fibonacci.json");
+    let expected_parameter_file = test_case_dir.join("cpu_air_params.json");
+
+    let result = invoke_cli(
+        &[
+            "--from-program",
+            program.to_str().unwrap(),
+            "--layout",
+            "starknet_with_keccak",
+        ],
+        &parameter_file,
+    )
+    .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+    let expected_parameters: ProverParameters =
+        read_json_from_file(expected_parameter_file).unwrap();
+    assert_eq!(parameters, expected_parameters);
+}
+
+#[rstest]
+fn generate_params_for_l1_verifier(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let parameter_file = output_dir.path().join("cpu_air_params.json");
+
+    let result = invoke_cli(&["--n-steps", "32768", "--verifier", "l1"], &parameter_file)
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let parameters: ProverParameters = read_json_from_file(parameter_file).unwrap();
+    assert_eq!(parameters.stark.fri.last_layer_degree_bound, 32);
+}
+
+#[rstest]
+#[case("0")]
+#[case("2147483649")]
+fn generate_params_with_invalid_n_steps(#[from(cli_in_path)] _path: (), #[case] n_steps: &str) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let parameter_file = output_dir.path().join("cpu_air_params.json");
+
+    let result =
+        invoke_cli(&["--n-steps", n_steps], &parameter_file).expect("Command should succeed");
+    assert!(!result.status.success());
+    assert!(!parameter_file.exists());
+}
@@ -0,0 +1,115 @@
+use std::path::Path;
+
+use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::Proof;
+
+use crate::common::cli_in_path;
+
+mod common;
+
+fn invoke_cli(
+    proof_file: &Path,
+    format: Option<&str>,
+) -> Result<std::process::Output, std::io::Error> {
+    let mut command = std::process::Command::new("stone-prover-cli");
+    command.arg("proof").arg("inspect").arg(proof_file);
+
+    if let Some(format) = format {
+        command.arg("--format").arg(format);
+    }
+
+    command.output()
+}
+
+#[rstest]
+fn inspect_proof_json(#[from(cli_in_path)] _path: ()) {
+    let proof_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/proof.json");
+
+    let result = invoke_cli(proof_file.as_path(), Some("json")).expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let summary: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    let proof: Proof = read_json_from_file(proof_file).unwrap();
+
+    assert_eq!(
+        summary["layout"],
+        serde_json::to_value(&proof.public_input.layout).unwrap()
+    );
+    assert_eq!(summary["n_steps"], proof.public_input.n_steps);
+    assert_eq!(
+        summary["fri"]["n_queries"],
+        proof.proof_parameters.stark.fri.n_queries
+    );
+}
+
+#[rstest]
+fn inspect_proof_text(#[from(cli_in_path)] _path: ()) {
+    let proof_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/proof.json");
+
+    let result = invoke_cli(proof_file.as_path(), None).expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    assert!(stdout.contains("Layout:"));
+    assert!(stdout.contains("n_queries:"));
+}
+
+#[rstest]
+fn split_proof_for_l1_verifier(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let proof_file = output_dir.path().join("proof.json");
+    let split_proof_dir = output_dir.path().join("split-proof");
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(program)
+        .arg("--verifier")
+        .arg("l1")
+        .arg("--output-file")
+        .arg(&proof_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("proof")
+        .arg("split")
+        .arg(&proof_file)
+        .arg("--output-dir")
+        .arg(&split_proof_dir)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    for file in [
+        "annotated_proof.json",
+        "main_proof.json",
+        "trace_merkle_statements.json",
+        "fri_merkle_statements.json",
+        "memory_pages.json",
+    ] {
+        assert!(split_proof_dir.join(file).exists(), "{file} is missing");
+    }
+}
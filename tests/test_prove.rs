@@ -20,6 +20,8 @@ fn invoke_cli(
     let mut command = std::process::Command::new("stone-prover-cli");
 
     command.arg("prove");
+    // The expected proofs were generated with this layout
+    command.arg("--layout").arg("starknet_with_keccak");
 
     if with_bootloader {
         command.arg("--with-bootloader");
@@ -169,6 +171,146 @@ fn execute_and_prove_program_l1_verifier(#[from(cli_in_path)] _path: ()) {
     assert_eq!(proof.proof_parameters.stark.fri.last_layer_degree_bound, 32);
 }
 
+#[rstest]
+fn execute_and_prove_program_with_output_values(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let proof_file = output_dir.path().join("proof.json");
+    let output_values_file = output_dir.path().join("output.json");
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(&program)
+        .arg("--output-file")
+        .arg(&proof_file)
+        .arg("--print-output")
+        .arg("--output-values-file")
+        .arg(&output_values_file)
+        .arg("--output-encoding")
+        .arg("hex")
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(String::from_utf8_lossy(&result.stdout).contains("Program output:"));
+
+    let output_values: Vec<String> = read_json_from_file(output_values_file).unwrap();
+    assert_eq!(output_values.len(), 2);
+    assert!(output_values.iter().all(|value| value.starts_with("0x")));
+}
+
+#[rstest]
+fn execute_and_prove_sierra_program_with_args(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let proof_file = output_dir.path().join("proof.json");
+    let output_values_file = output_dir.path().join("output.json");
+
+    // `main` returns the sum of its two arguments
+    let program =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/sierra_args.sierra.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(&program)
+        .arg("--args")
+        .arg("2 3")
+        .arg("--output-file")
+        .arg(&proof_file)
+        .arg("--output-values-file")
+        .arg(&output_values_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let output_values: Vec<String> = read_json_from_file(output_values_file).unwrap();
+    assert_eq!(output_values, vec!["5".to_string()]);
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("verify")
+        .arg(&proof_file)
+        .arg("--expected-output")
+        .arg("[5]")
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // The Cairo 1 runner initializes all the builtins of the program
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(&program)
+        .arg("--args")
+        .arg("2 3")
+        .arg("--allow-missing-builtins")
+        .arg("--output-file")
+        .arg(&proof_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(!result.status.success());
+
+    // The Cairo 1 runner cannot be interrupted to enforce a step limit
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(&program)
+        .arg("--args")
+        .arg("2 3")
+        .arg("--max-steps")
+        .arg("1000000")
+        .arg("--output-file")
+        .arg(&proof_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("--max-steps"));
+}
+
+#[rstest]
+fn execute_and_prove_program_keeping_artifacts(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let proof_file = output_dir.path().join("proof.json");
+    let artifacts_dir = output_dir.path().join("artifacts");
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg(&program)
+        .arg("--output-file")
+        .arg(&proof_file)
+        .arg("--keep-artifacts")
+        .arg(&artifacts_dir)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    for file in [
+        "public_input.json",
+        "private_input.json",
+        "memory.bin",
+        "trace.bin",
+        "cpu_air_prover_config.json",
+        "cpu_air_params.json",
+    ] {
+        assert!(artifacts_dir.join(file).exists(), "{file} is missing");
+    }
+}
+
 #[rstest]
 fn execute_and_prove_program_with_bootloader(#[from(cli_in_path)] _path: ()) {
     let output_dir = tempfile::tempdir().unwrap();
@@ -236,3 +378,86 @@ fn execute_and_prove_pie_with_bootloader(#[from(cli_in_path)] _path: ()) {
     let expected_proof: Proof = read_json_from_file(expected_proof).unwrap();
     assert_proof_eq(proof, expected_proof);
 }
+
+#[rstest]
+fn prove_pre_computed_execution_artifacts(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let artifacts_dir = output_dir.path().join("artifacts");
+    let proof_file = output_dir.path().join("proof.json");
+
+    let test_case_dir =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("dependencies/cairo-programs/cairo0/fibonacci");
+    let program = test_case_dir.join("fibonacci.json");
+    let expected_proof = test_case_dir.join("proof.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(program)
+        .arg("--layout")
+        .arg("starknet_with_keccak")
+        .arg("--output-dir")
+        .arg(&artifacts_dir)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg("--public-input")
+        .arg(artifacts_dir.join("public_input.json"))
+        .arg("--private-input")
+        .arg(artifacts_dir.join("private_input.json"))
+        .arg("--memory")
+        .arg(artifacts_dir.join("memory.bin"))
+        .arg("--trace")
+        .arg(artifacts_dir.join("trace.bin"))
+        .arg("--output-file")
+        .arg(&proof_file)
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let proof: Proof = read_json_from_file(proof_file).unwrap();
+    let expected_proof: Proof = read_json_from_file(expected_proof).unwrap();
+    assert_proof_eq(proof, expected_proof);
+}
+
+#[rstest]
+#[case(&["--allow-missing-builtins"])]
+#[case(&["--cairo-pie-output", "cairo_pie.zip"])]
+#[case(&["--entrypoint", "main"])]
+#[case(&["--task-manifest", "tasks.json"])]
+#[case(&["--bootloader-input", "bootloader_input.json"])]
+fn prove_pre_computed_execution_artifacts_with_execution_args(
+    #[from(cli_in_path)] _path: (),
+    #[case] execution_args: &[&str],
+) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    // Rejected by the argument parser, the artifacts are never read
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("prove")
+        .arg("--public-input")
+        .arg(output_dir.path().join("public_input.json"))
+        .arg("--private-input")
+        .arg(output_dir.path().join("private_input.json"))
+        .arg("--memory")
+        .arg(output_dir.path().join("memory.bin"))
+        .arg("--trace")
+        .arg(output_dir.path().join("trace.bin"))
+        .args(execution_args)
+        .arg("--output-file")
+        .arg(output_dir.path().join("proof.json"))
+        .output()
+        .expect("Command should succeed");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("cannot be used with"));
+}
@@ -0,0 +1,645 @@
+use std::path::Path;
+
+use cairo_vm::vm::runners::cairo_pie::CairoPie;
+use cairo_vm::Felt252;
+use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::{Proof, PublicInput};
+
+use crate::common::cli_in_path;
+
+mod common;
+
+fn invoke_cli(
+    with_bootloader: bool,
+    executables: &[&Path],
+    output_dir: &Path,
+    cairo_pie_output: Option<&Path>,
+) -> Result<std::process::Output, std::io::Error> {
+    let mut command = std::process::Command::new("stone-prover-cli");
+
+    command.arg("run");
+
+    if with_bootloader {
+        command.arg("--with-bootloader");
+    }
+    for executable in executables {
+        command.arg(*executable);
+    }
+    // The expected artifacts were generated with this layout
+    command.arg("--layout").arg("starknet_with_keccak");
+    command.arg("--output-dir").arg(output_dir);
+    if let Some(cairo_pie_output) = cairo_pie_output {
+        command.arg("--cairo-pie-output").arg(cairo_pie_output);
+    }
+
+    command.output()
+}
+
+#[rstest]
+fn execute_program(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let test_case_dir =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("dependencies/cairo-programs/cairo0/fibonacci");
+    let program = test_case_dir.join("fibonacci.json");
+    let expected_proof = test_case_dir.join("proof.json");
+
+    let result = invoke_cli(false, &[program.as_path()], output_dir.path(), None)
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    for file in [
+        "public_input.json",
+        "private_input.json",
+        "memory.bin",
+        "trace.bin",
+    ] {
+        assert!(output_dir.path().join(file).exists(), "{file} is missing");
+    }
+
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    let expected_proof: Proof = read_json_from_file(expected_proof).unwrap();
+    assert_eq!(public_input, expected_proof.public_input);
+
+    let private_input: serde_json::Value =
+        read_json_from_file(output_dir.path().join("private_input.json")).unwrap();
+    for key in ["trace_path", "memory_path"] {
+        let path = private_input[key].as_str().unwrap();
+        assert!(Path::new(path).is_absolute(), "{key} is relative: {path}");
+    }
+}
+
+#[rstest]
+fn execute_program_with_bootloader(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let test_case_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/programs/fibonacci");
+    let program = test_case_dir.join("program.json");
+
+    let result = invoke_cli(true, &[program.as_path()], output_dir.path(), None)
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(output_dir.path().join("public_input.json").exists());
+}
+
+#[rstest]
+fn execute_program_and_export_cairo_pie(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let cairo_pie_file = output_dir.path().join("cairo_pie.zip");
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = invoke_cli(
+        false,
+        &[program.as_path()],
+        output_dir.path(),
+        Some(cairo_pie_file.as_path()),
+    )
+    .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let cairo_pie = CairoPie::from_file(&cairo_pie_file).expect("Cairo PIE should be valid");
+    assert!(cairo_pie.execution_resources.n_steps > 0);
+}
+
+#[rstest]
+#[case("--max-steps")]
+#[case("--max-memory-cells")]
+fn execute_program_exceeding_limit(#[from(cli_in_path)] _path: (), #[case] limit: &str) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg(limit)
+        .arg("10")
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("exceeded the limit of 10"));
+    assert!(!output_dir.path().join("public_input.json").exists());
+}
+
+#[rstest]
+fn execute_program_with_json_report(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--layout")
+        .arg("starknet_with_keccak")
+        .arg("--report")
+        .arg("json")
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(report["trace_length"], public_input.n_steps);
+    let n_steps = report["n_steps"].as_u64().unwrap();
+    let padding_steps = report["padding_steps"].as_u64().unwrap();
+    assert!(n_steps > 0);
+    assert_eq!(n_steps + padding_steps, public_input.n_steps as u64);
+    assert!(report["builtins"]["output"]["used_cells"]
+        .as_u64()
+        .is_some());
+}
+
+#[rstest]
+fn execute_program_with_bootloader_and_selected_layout(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg("--with-bootloader")
+        .arg(&program)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // The task does not use the builtins that only starknet_with_keccak provides
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    assert_ne!(public_input.layout.to_string(), "starknet_with_keccak");
+}
+
+#[rstest]
+fn execute_program_with_dynamic_layout(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--layout")
+        .arg("dynamic")
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    // Rejected before running the program
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("dynamic layout is not supported"));
+    assert!(!output_dir.path().join("public_input.json").exists());
+}
+
+#[rstest]
+fn execute_program_with_cairo_run_files(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let cairo_run_dir = tempfile::tempdir().unwrap();
+    let trace_file = cairo_run_dir.path().join("trace.bin");
+    let memory_file = cairo_run_dir.path().join("memory.bin");
+    let private_input_file = cairo_run_dir.path().join("air_private_input.json");
+
+    let programs_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(programs_dir.join("entrypoint.json"))
+        .arg("--trace-file")
+        .arg(&trace_file)
+        .arg("--memory-file")
+        .arg(&memory_file)
+        .arg("--air-public-input")
+        .arg(cairo_run_dir.path().join("air_public_input.json"))
+        .arg("--air-private-input")
+        .arg(&private_input_file)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // Trace entries hold the ap, fp and pc registers, memory cells an address and a value
+    for (file, entry_size) in [(&trace_file, 24), (&memory_file, 40)] {
+        let size = std::fs::metadata(file).unwrap().len();
+        assert!(size > 0 && size % entry_size == 0, "{}", file.display());
+    }
+    let _: PublicInput =
+        read_json_from_file(cairo_run_dir.path().join("air_public_input.json")).unwrap();
+    let private_input: serde_json::Value = read_json_from_file(private_input_file).unwrap();
+    assert_eq!(
+        private_input["trace_path"],
+        trace_file.to_string_lossy().as_ref()
+    );
+    assert_eq!(
+        private_input["memory_path"],
+        memory_file.to_string_lossy().as_ref()
+    );
+}
+
+#[rstest]
+fn execute_program_with_profile(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let profile_file = output_dir.path().join("profile.folded");
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--profile")
+        .arg(&profile_file)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let profile = std::fs::read_to_string(&profile_file).unwrap();
+    let stacks: Vec<(&str, usize)> = profile
+        .lines()
+        .map(|line| {
+            let (stack, steps) = line.rsplit_once(' ').unwrap();
+            (stack, steps.parse().unwrap())
+        })
+        .collect();
+    assert!(stacks
+        .iter()
+        .any(|(stack, _)| stack.starts_with("__main__.main")));
+
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    let profiled_steps: usize = stacks.iter().map(|(_, steps)| steps).sum();
+    assert!(profiled_steps <= public_input.n_steps as usize);
+}
+
+#[rstest]
+fn execute_program_with_automatic_layout(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/fibonacci.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // The program only uses the output builtin, the recursive layout has the fewest
+    // trace cells among the layouts with it
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    assert_eq!(
+        serde_json::to_value(&public_input.layout).unwrap(),
+        serde_json::json!("recursive")
+    );
+}
+
+/// Reads the values of the output segment from the public memory.
+fn read_output(public_input: &PublicInput) -> Vec<Felt252> {
+    let output_segment = &public_input.memory_segments["output"];
+    (output_segment.begin_addr..output_segment.stop_ptr)
+        .map(|address| {
+            let entry = public_input
+                .public_memory
+                .iter()
+                .find(|entry| entry.address == address)
+                .unwrap();
+            Felt252::from_hex(&entry.value).unwrap()
+        })
+        .collect()
+}
+
+#[rstest]
+fn execute_program_with_program_input(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let program_input_file = output_dir.path().join("input.json");
+    std::fs::write(&program_input_file, r#"{"n": 42}"#).unwrap();
+
+    // Its hint assigns `program_input['n']` to a local variable, which is then written
+    // to the output.
+    let program = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/program_input.json");
+
+    let run_with_input = |program_input: &Path| {
+        std::process::Command::new("stone-prover-cli")
+            .arg("run")
+            .arg(&program)
+            .arg("--program-input")
+            .arg(program_input)
+            .arg("--output-dir")
+            .arg(output_dir.path())
+            .output()
+            .expect("Command should succeed")
+    };
+
+    let result = run_with_input(&program_input_file);
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    assert_eq!(read_output(&public_input), vec![Felt252::from(42)]);
+
+    // The hint reads a key missing from the input
+    std::fs::write(&program_input_file, r#"{"m": 42}"#).unwrap();
+    let result = run_with_input(&program_input_file);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("no `n` key"));
+
+    // The program input must be valid JSON
+    std::fs::write(&program_input_file, "not json").unwrap();
+    let result = run_with_input(&program_input_file);
+    assert!(!result.status.success());
+}
+
+#[rstest]
+fn execute_program_with_list_program_input(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let program_input_file = output_dir.path().join("input.json");
+    std::fs::write(
+        &program_input_file,
+        r#"{"config": {"values": [5, "0x7", [1, 2]]}}"#,
+    )
+    .unwrap();
+
+    // Its hint writes `program_input['config']['values']` to a segment with
+    // `segments.gen_arg`, then the program writes the length and the second value to the output.
+    let program =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/program_input_list.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--program-input")
+        .arg(&program_input_file)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    assert_eq!(
+        read_output(&public_input),
+        vec![Felt252::from(3), Felt252::from(7)]
+    );
+}
+
+#[rstest]
+fn execute_task_manifest_with_bootloader(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let task_manifest = output_dir.path().join("tasks.toml");
+    let program_input_file = output_dir.path().join("input.json");
+    std::fs::write(&program_input_file, r#"{"n": 42}"#).unwrap();
+
+    let program_with_input =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/program_input.json");
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/programs/fibonacci/program.json");
+    let manifest = format!(
+        r#"
+        [[tasks]]
+        path = "{program_with_input}"
+        program_input = "input.json"
+
+        [[tasks]]
+        path = "{program}"
+        "#,
+        program_with_input = program_with_input.to_string_lossy(),
+        program = program.to_string_lossy()
+    );
+    std::fs::write(&task_manifest, manifest).unwrap();
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg("--with-bootloader")
+        .arg("--task-manifest")
+        .arg(&task_manifest)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // Bootloader output: number of tasks, then the size, program hash and output of each task
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    let output = read_output(&public_input);
+    assert_eq!(output[0], Felt252::from(2));
+    assert_eq!(output[1], Felt252::from(3));
+    assert_eq!(output[3], Felt252::from(42));
+}
+
+#[rstest]
+fn execute_bootloader_input(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let bootloader_input = output_dir.path().join("bootloader_input.json");
+
+    let pie = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/pies/fibonacci-stone-e2e/cairo_pie.zip");
+    let input = serde_json::json!({
+        "tasks": [{"type": "CairoPiePath", "path": pie, "use_poseidon": false}],
+        "single_page": false,
+    });
+    std::fs::write(&bootloader_input, input.to_string()).unwrap();
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg("--with-bootloader")
+        .arg("--bootloader-input")
+        .arg(&bootloader_input)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(output_dir.path().join("public_input.json").exists());
+}
+
+#[rstest]
+fn execute_unzipped_pie_with_bootloader(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let pie_dir = output_dir.path().join("cairo_pie");
+
+    let pie = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/pies/fibonacci-stone-e2e/cairo_pie.zip");
+    let mut zip_archive = zip::ZipArchive::new(std::fs::File::open(pie).unwrap()).unwrap();
+    zip_archive.extract(&pie_dir).unwrap();
+
+    let result = invoke_cli(true, &[pie_dir.as_path()], output_dir.path(), None)
+        .expect("Command should succeed");
+
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+    assert!(output_dir.path().join("public_input.json").exists());
+}
+
+#[rstest]
+fn execute_task_with_entrypoint(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    let program = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/bootloader/programs/fibonacci/program.json");
+
+    let run_with_entrypoint = |with_bootloader: bool, entrypoint: &str| {
+        let mut command = std::process::Command::new("stone-prover-cli");
+        command.arg("run");
+        if with_bootloader {
+            command.arg("--with-bootloader");
+        }
+        command
+            .arg(&program)
+            .arg("--entrypoint")
+            .arg(entrypoint)
+            .arg("--output-dir")
+            .arg(output_dir.path())
+            .output()
+            .expect("Command should succeed")
+    };
+
+    let result = run_with_entrypoint(true, "main");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // The entrypoint must exist in the program
+    assert!(!run_with_entrypoint(true, "missing").status.success());
+}
+
+#[rstest]
+#[case("main", 1)]
+#[case("write_two", 2)]
+fn execute_program_with_entrypoint(
+    #[from(cli_in_path)] _path: (),
+    #[case] entrypoint: &str,
+    #[case] expected_output: u64,
+) {
+    let output_dir = tempfile::tempdir().unwrap();
+
+    // `main` writes 1 to the output and `write_two` writes 2
+    let program = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/entrypoint.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--entrypoint")
+        .arg(entrypoint)
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let public_input: PublicInput =
+        read_json_from_file(output_dir.path().join("public_input.json")).unwrap();
+    assert_eq!(
+        read_output(&public_input),
+        vec![Felt252::from(expected_output)]
+    );
+}
+
+#[rstest]
+fn execute_program_with_missing_entrypoint(#[from(cli_in_path)] _path: ()) {
+    let output_dir = tempfile::tempdir().unwrap();
+    let program = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs/entrypoint.json");
+
+    let result = std::process::Command::new("stone-prover-cli")
+        .arg("run")
+        .arg(&program)
+        .arg("--entrypoint")
+        .arg("missing")
+        .arg("--output-dir")
+        .arg(output_dir.path())
+        .output()
+        .expect("Command should succeed");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("has no `missing` function"));
+}
@@ -1,6 +1,8 @@
 use std::path::Path;
 
 use rstest::rstest;
+use stone_prover_sdk::json::read_json_from_file;
+use stone_prover_sdk::models::Proof;
 
 use crate::common::cli_in_path;
 
@@ -30,3 +32,63 @@ fn test_verify_pie_with_bootloader(#[from(cli_in_path)] _path: ()) {
 
     invoke_cli(proof_file.as_path()).expect("Command should succeed");
 }
+
+fn invoke_cli_with_expected_output(
+    proof_file: &Path,
+    expected_output: &str,
+) -> Result<std::process::Output, std::io::Error> {
+    let mut command = std::process::Command::new("stone-prover-cli");
+    command
+        .arg("verify")
+        .arg(proof_file)
+        .arg("--expected-output")
+        .arg(expected_output);
+
+    command.output()
+}
+
+/// Reads the output segment from the public memory of the proof.
+fn read_output(proof_file: &Path) -> Vec<String> {
+    let proof: Proof = read_json_from_file(proof_file).unwrap();
+    let output_segment = &proof.public_input.memory_segments["output"];
+    (output_segment.begin_addr..output_segment.stop_ptr)
+        .map(|address| {
+            proof
+                .public_input
+                .public_memory
+                .iter()
+                .find(|entry| entry.address == address)
+                .unwrap()
+                .value
+                .clone()
+        })
+        .collect()
+}
+
+#[rstest]
+fn test_verify_program_with_expected_output(#[from(cli_in_path)] _path: ()) {
+    let proof_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/proof.json");
+    let expected_output = read_output(&proof_file).join(",");
+
+    let result = invoke_cli_with_expected_output(&proof_file, &expected_output)
+        .expect("Command should succeed");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+}
+
+#[rstest]
+fn test_verify_program_with_wrong_expected_output(#[from(cli_in_path)] _path: ()) {
+    let proof_file = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("dependencies/cairo-programs/cairo0/fibonacci/proof.json");
+    let mut expected_output = read_output(&proof_file);
+    expected_output.push("0x0".to_string());
+
+    let result = invoke_cli_with_expected_output(&proof_file, &expected_output.join(","))
+        .expect("Command should succeed");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("does not match"));
+}